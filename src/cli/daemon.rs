@@ -0,0 +1,207 @@
+use std::time::Duration;
+
+use anyhow::anyhow;
+use clap::ArgMatches;
+use log4rs::Handle;
+use reqwest::blocking::Client;
+use zabbix_api::client::client::ZabbixApiClientImpl;
+
+use crate::cli::signals::{
+    install_signal_handlers, is_terminate_requested, take_reload_request, wait_for_signal,
+};
+use crate::cli::{
+    get_cli_logging_config, get_generate_options, get_url_provider, get_url_source_type,
+    init_logging, init_rate_limit, load_cli_config, write_report, INTERVAL_ARG, REPORT_FILE_ARG,
+};
+use crate::command::generate::items::generate_web_scenarios_and_triggers;
+use crate::command::generate::report::Report;
+use crate::command::generate::GenerateOptions;
+use crate::config::duration::parse_duration;
+use crate::config::AppConfig;
+use crate::types::{AppError, AppResult};
+use crate::zabbix::auth::{is_session_expired, ZabbixSession};
+
+/// Runs generation every interval until SIGTERM or SIGINT, current run is finished first.
+/// Config is reloaded on SIGHUP and applied right away. Zabbix session is kept between runs
+/// and renewed when it expires.
+///
+/// Errors of a single run are logged, daemon keeps running.
+pub fn run_daemon(root_matches: &ArgMatches, matches: &ArgMatches) -> AppResult<()> {
+    let mut config = load_cli_config(root_matches)?;
+
+    let logging_handle = init_logging(root_matches, &config.logging).map_err(AppError::Config)?;
+
+    let url_source_type = get_url_source_type(matches)?;
+    let options = get_generate_options(matches, false);
+    let report_file = matches.get_one::<String>(REPORT_FILE_ARG);
+    let mut interval = get_interval(matches, &config)?;
+
+    init_rate_limit(matches);
+
+    install_signal_handlers().map_err(AppError::Other)?;
+
+    info!("daemon has been started, interval {}s", interval.as_secs());
+
+    let mut zabbix_client = get_zabbix_client(&config);
+    let mut session = ZabbixSession::default();
+
+    while !is_terminate_requested() {
+        if take_reload_request() {
+            match reload_config(root_matches, matches, &logging_handle) {
+                Ok((reloaded_config, reloaded_interval)) => {
+                    session.close(&zabbix_client, &config.zabbix.api);
+
+                    config = reloaded_config;
+                    interval = reloaded_interval;
+                    zabbix_client = get_zabbix_client(&config);
+
+                    info!("config has been reloaded, interval {}s", interval.as_secs());
+                }
+                Err(e) => {
+                    error!("config reload error, previous config is kept: {e}");
+                    error!("{}", e.root_cause());
+                }
+            }
+        }
+
+        let result = run_generation(
+            matches,
+            url_source_type,
+            &config,
+            &zabbix_client,
+            &mut session,
+            &options,
+        );
+
+        match result {
+            Ok(report) => log_report(&report, options.strict, report_file),
+            Err(e) => {
+                error!("generation error: {e}");
+                error!("{}", e.root_cause());
+            }
+        }
+
+        wait_for_signal(interval);
+    }
+
+    session.close(&zabbix_client, &config.zabbix.api);
+
+    info!("daemon has been stopped");
+
+    Ok(())
+}
+
+/// Returns config and interval. Logging is reconfigured as well
+fn reload_config(
+    root_matches: &ArgMatches,
+    matches: &ArgMatches,
+    logging_handle: &Handle,
+) -> AppResult<(AppConfig, Duration)> {
+    info!("reload config..");
+
+    let config = load_cli_config(root_matches)?;
+    let interval = get_interval(matches, &config)?;
+
+    let logging_config = get_cli_logging_config(root_matches, &config.logging)
+        .map_err(AppError::Config)?;
+    logging_handle.set_config(logging_config);
+
+    Ok((config, interval))
+}
+
+/// Run is repeated once with a new session when the current one has expired
+fn run_generation(
+    matches: &ArgMatches,
+    url_source_type: &str,
+    config: &AppConfig,
+    zabbix_client: &ZabbixApiClientImpl,
+    session: &mut ZabbixSession,
+    options: &GenerateOptions,
+) -> AppResult<Report> {
+    let mut renewed = false;
+
+    loop {
+        let session_id = session.get(zabbix_client, &config.zabbix.api)?;
+
+        let result = generate_web_scenarios_and_triggers(
+            zabbix_client,
+            &session_id,
+            get_url_provider(matches, url_source_type, config, zabbix_client, &session_id),
+            &config.zabbix,
+            options,
+        );
+
+        if !renewed && has_expired_session(&result) {
+            warn!("zabbix session has expired, login again");
+            session.expire();
+            renewed = true;
+            continue;
+        }
+
+        return result;
+    }
+}
+
+fn has_expired_session(result: &AppResult<Report>) -> bool {
+    match result {
+        Ok(report) => report.failures.iter().any(|failure| is_session_expired(&failure.root_cause)),
+        Err(e) => is_session_expired(&e.root_cause()),
+    }
+}
+
+fn log_report(report: &Report, strict: bool, report_file: Option<&String>) {
+    info!("run report:\n{report}");
+
+    if let Some(report_file) = report_file {
+        if let Err(e) = write_report(report, report_file) {
+            error!("unable to write report file '{report_file}': {e}");
+        }
+    }
+
+    if let Some(e) = report.get_error(strict) {
+        error!("{e}");
+    }
+}
+
+/// `--interval` option overrides `daemon.interval` config value
+fn get_interval(matches: &ArgMatches, config: &AppConfig) -> AppResult<Duration> {
+    let interval = match matches.get_one::<String>(INTERVAL_ARG) {
+        Some(interval) => interval,
+        None => &config.daemon.interval,
+    };
+
+    let interval = parse_duration(interval).map_err(AppError::Config)?;
+
+    if interval.is_zero() {
+        return Err(AppError::Config(anyhow!("daemon interval must be greater than zero")));
+    }
+
+    Ok(interval)
+}
+
+fn get_zabbix_client(config: &AppConfig) -> ZabbixApiClientImpl {
+    ZabbixApiClientImpl::new(Client::new(), &config.zabbix.api.endpoint)
+}
+
+#[cfg(test)]
+mod tests {
+    use anyhow::anyhow;
+
+    use crate::cli::daemon::has_expired_session;
+    use crate::command::generate::report::{Report, UrlFailure};
+    use crate::types::AppError;
+
+    #[test]
+    fn expired_session_should_be_detected_in_errors_and_failures() {
+        let expired = anyhow!("Session terminated, re-login, please.");
+
+        assert!(has_expired_session(&Err(AppError::Api(anyhow!("Session terminated, re-login, please.")))));
+        assert!(!has_expired_session(&Err(AppError::Other(anyhow!("connection refused")))));
+        assert!(!has_expired_session(&Ok(Report::default())));
+
+        let mut report = Report::default();
+        report.failures.push(UrlFailure::new("srv1", "https://a.com", &expired.context("unable to create item")));
+
+        assert!(has_expired_session(&Ok(report)));
+    }
+}
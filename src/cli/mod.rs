@@ -1,40 +1,103 @@
+#[cfg(unix)]
+pub mod daemon;
+#[cfg(unix)]
+pub mod signals;
+
 use crate::command::generate::items::generate_web_scenarios_and_triggers;
+use crate::command::generate::report::Report;
+use crate::command::generate::GenerateOptions;
 use crate::config::file::load_config_from_file;
+use crate::config::logging::{LogFormat, LogTarget, LoggingConfig};
+use crate::config::AppConfig;
 use crate::logging::get_logging_config;
 use crate::source::file::FileUrlSourceProvider;
+use crate::source::structured::{StructuredFormat, StructuredUrlSourceProvider};
 use crate::source::zabbix::ZabbixUrlSourceProvider;
-use clap::{Arg, ArgMatches, Command};
+use crate::source::UrlSourceProvider;
+#[cfg(unix)]
+use crate::cli::daemon::run_daemon;
+use crate::types::{AppError, AppResult, EmptyResult, OperationResult};
+use crate::zabbix::api::set_rate_limit;
+use crate::zabbix::auth::{login, logout};
+use anyhow::{anyhow, Context};
+use clap::parser::ValueSource;
+use clap::{Arg, ArgAction, ArgMatches, Command};
+use log4rs::config::Config;
+use log4rs::Handle;
 use reqwest::blocking::Client;
 use std::env;
+use std::fs;
 use std::path::Path;
 use std::process::exit;
 use zabbix_api::client::client::ZabbixApiClientImpl;
 
 pub const GENERATE_COMMAND: &str = "gen";
+pub const DAEMON_COMMAND: &str = "daemon";
 
 pub const SOURCE_ARG: &str = "source";
 pub const SOURCE_ARG_DEFAULT_VALUE: &str = "zabbix";
 pub const SOURCE_ARG_FILE_VALUE: &str = "file";
+pub const SOURCE_ARG_YAML_VALUE: &str = "yaml";
+pub const SOURCE_ARG_JSON_VALUE: &str = "json";
 
 pub const FILE_ARG: &str = "file";
 pub const FILE_ARG_DEFAULT_VALUE: &str = "urls.txt";
+pub const FILE_ARG_YAML_DEFAULT_VALUE: &str = "urls.yml";
+pub const FILE_ARG_JSON_DEFAULT_VALUE: &str = "urls.json";
 pub const FILE_SHORT_ARG: &str = "f";
 pub const SOURCE_SHORT_ARG: &str = "s";
 pub const ITEM_KEY_SEARCH_MASK_ARG: &str = "item-key-starts-with";
 pub const ITEM_KEY_SEARCH_MASK_DEFAULT_VALUE: &str = "nginx.vhost.item";
 
+pub const SYNC_ARG: &str = "sync";
+pub const PRUNE_ARG: &str = "prune";
+pub const DRY_RUN_ARG: &str = "dry-run";
+pub const STRICT_ARG: &str = "strict";
+pub const KEEP_GOING_ARG: &str = "keep-going";
+pub const PLAN_FORMAT_ARG: &str = "plan-format";
+pub const PLAN_FORMAT_DEFAULT_VALUE: &str = "text";
+pub const PLAN_FORMAT_JSON_VALUE: &str = "json";
+pub const REPORT_FILE_ARG: &str = "report-file";
+pub const CONCURRENCY_ARG: &str = "concurrency";
+pub const CONCURRENCY_DEFAULT_VALUE: &str = "1";
+pub const RATE_LIMIT_ARG: &str = "rate-limit";
+pub const RATE_LIMIT_DEFAULT_VALUE: &str = "0";
+pub const INTERVAL_ARG: &str = "interval";
+
 pub const WORK_DIR_ARG: &str = "work-dir";
 pub const WORK_DIR_SHORT_ARG: &str = "d";
 pub const WORK_DIR_DEFAULT_VALUE: &str = ".";
 
+pub const CONFIG_ARG: &str = "config";
+pub const CONFIG_SHORT_ARG: &str = "c";
+pub const CONFIG_ENV_VAR: &str = "WSZL_CONFIG";
+pub const CONFIG_DEFAULT_VALUE: &str = "wszl.yml";
+
+pub const PROFILE_ARG: &str = "profile";
+pub const PROFILE_ENV_VAR: &str = "WSZL_PROFILE";
+
 pub const LOG_LEVEL_ARG: &str = "log-level";
 pub const LOG_LEVEL_DEFAULT_VALUE: &str = "info";
+pub const LOG_TARGET_ARG: &str = "log-target";
+pub const LOG_TARGET_FILE_VALUE: &str = "file";
+pub const LOG_TARGET_STDOUT_VALUE: &str = "stdout";
+pub const LOG_TARGET_STDERR_VALUE: &str = "stderr";
+pub const LOG_TARGET_SYSLOG_VALUE: &str = "syslog";
+pub const LOG_FILE_ARG: &str = "log-file";
+pub const LOG_FORMAT_ARG: &str = "log-format";
+pub const LOG_FORMAT_TEXT_VALUE: &str = "text";
+pub const LOG_FORMAT_JSON_VALUE: &str = "json";
 
 pub const OK_EXIT_CODE: i32 = 0;
 pub const ERROR_EXIT_CODE: i32 = 1;
+pub const CONFIG_ERROR_EXIT_CODE: i32 = 3;
+pub const AUTH_ERROR_EXIT_CODE: i32 = 4;
+pub const SOURCE_ERROR_EXIT_CODE: i32 = 5;
+pub const PARTIAL_FAILURE_EXIT_CODE: i32 = 6;
+pub const API_ERROR_EXIT_CODE: i32 = 7;
 
 pub fn get_cli_app() -> ArgMatches {
-    let matches = Command::new("WSZL tool")
+    let app = Command::new("WSZL tool")
         .version("1.1.0")
         .author("Eugene Lebedev <duke.tougu@gmail.com>")
         .about("Add Web scenarios support for Zabbix Low Level Discovery")
@@ -47,6 +110,22 @@ pub fn get_cli_app() -> ArgMatches {
                 .required(false)
                 .default_value(WORK_DIR_DEFAULT_VALUE),
         )
+        .arg(
+            Arg::new(CONFIG_ARG)
+                .long(CONFIG_ARG)
+                .short('c')
+                .env(CONFIG_ENV_VAR)
+                .help("config file or directory with *.yml files (relative to working directory)")
+                .required(false)
+                .default_value(CONFIG_DEFAULT_VALUE),
+        )
+        .arg(
+            Arg::new(PROFILE_ARG)
+                .long(PROFILE_ARG)
+                .env(PROFILE_ENV_VAR)
+                .help("config profile name from 'profiles' section, i.e. production")
+                .required(false),
+        )
         .arg(
             Arg::new(LOG_LEVEL_ARG)
                 .long(LOG_LEVEL_ARG)
@@ -55,143 +134,399 @@ pub fn get_cli_app() -> ArgMatches {
                 .required(false)
                 .default_value(LOG_LEVEL_DEFAULT_VALUE),
         )
+        .arg(
+            Arg::new(LOG_TARGET_ARG)
+                .long(LOG_TARGET_ARG)
+                .help("log target: file, stdout, stderr, syslog. overrides `logging.target` config value")
+                .value_parser([
+                    LOG_TARGET_FILE_VALUE,
+                    LOG_TARGET_STDOUT_VALUE,
+                    LOG_TARGET_STDERR_VALUE,
+                    LOG_TARGET_SYSLOG_VALUE,
+                ])
+                .required(false),
+        )
+        .arg(
+            Arg::new(LOG_FILE_ARG)
+                .long(LOG_FILE_ARG)
+                .help("log file path for file target. overrides `logging.file` config value")
+                .required(false),
+        )
+        .arg(
+            Arg::new(LOG_FORMAT_ARG)
+                .long(LOG_FORMAT_ARG)
+                .help("log format: text, json (json lines). overrides `logging.format` config value")
+                .value_parser([LOG_FORMAT_TEXT_VALUE, LOG_FORMAT_JSON_VALUE])
+                .required(false),
+        )
         .subcommand(
             Command::new(GENERATE_COMMAND)
                 .about("generate web scenarios and triggers for zabbix items")
+                .args(get_generation_args())
                 .arg(
-                    Arg::new(SOURCE_ARG)
-                        .long(SOURCE_ARG)
-                        .short('s')
-                        .help("set urls source: zabbix, text-file")
-                        .default_value(SOURCE_ARG_DEFAULT_VALUE)
-                        .required(false),
-                )
-                .arg(
-                    Arg::new(FILE_ARG)
-                        .long(FILE_ARG)
-                        .short('f')
-                        .requires(SOURCE_ARG)
-                        .help("urls file name. Expected file format (per row): zabbix-host|url")
-                        .default_value(FILE_ARG_DEFAULT_VALUE)
+                    Arg::new(DRY_RUN_ARG)
+                        .long(DRY_RUN_ARG)
+                        .help("print plan of actions without changing anything in zabbix")
+                        .action(ArgAction::SetTrue)
                         .required(false),
                 )
                 .arg(
-                    Arg::new(ITEM_KEY_SEARCH_MASK_ARG)
-                        .long(ITEM_KEY_SEARCH_MASK_ARG)
-                        .help("set search mask for items")
-                        .default_value(ITEM_KEY_SEARCH_MASK_DEFAULT_VALUE)
+                    Arg::new(PLAN_FORMAT_ARG)
+                        .long(PLAN_FORMAT_ARG)
+                        .help("plan output format for dry run: text, json")
+                        .value_parser([PLAN_FORMAT_DEFAULT_VALUE, PLAN_FORMAT_JSON_VALUE])
+                        .default_value(PLAN_FORMAT_DEFAULT_VALUE)
                         .required(false),
                 ),
-        )
-        .get_matches();
+        );
+
+    #[cfg(unix)]
+    let app = app.subcommand(get_daemon_command());
+
+    let matches = app.get_matches();
 
     init_working_dir(&matches);
-    init_logging(&matches);
 
     matches
 }
 
+/// Daemon relies on unix signals
+#[cfg(unix)]
+fn get_daemon_command() -> Command {
+    Command::new(DAEMON_COMMAND)
+        .about("run generation periodically. config is reloaded on SIGHUP, SIGTERM stops daemon after current run")
+        .args(get_generation_args())
+        .arg(
+            Arg::new(INTERVAL_ARG)
+                .long(INTERVAL_ARG)
+                .help("pause between runs, i.e. 5m. overrides `daemon.interval` config value")
+                .required(false),
+        )
+}
+
+/// Shared by `gen` and `daemon` commands
+fn get_generation_args() -> Vec<Arg> {
+    vec![
+        Arg::new(SOURCE_ARG)
+            .long(SOURCE_ARG)
+            .short('s')
+            .help("set urls source: zabbix, file, yaml, json")
+            .default_value(SOURCE_ARG_DEFAULT_VALUE)
+            .required(false),
+        Arg::new(FILE_ARG)
+            .long(FILE_ARG)
+            .short('f')
+            .requires(SOURCE_ARG)
+            .help("urls file name. Expected file format (per row): zabbix-host|url[|options]. \
+                   Default for yaml and json sources: urls.yml, urls.json")
+            .default_value(FILE_ARG_DEFAULT_VALUE)
+            .required(false),
+        Arg::new(ITEM_KEY_SEARCH_MASK_ARG)
+            .long(ITEM_KEY_SEARCH_MASK_ARG)
+            .help("set search mask for items")
+            .default_value(ITEM_KEY_SEARCH_MASK_DEFAULT_VALUE)
+            .required(false),
+        Arg::new(SYNC_ARG)
+            .long(SYNC_ARG)
+            .help("update existing web scenarios, items and triggers which differ from config")
+            .action(ArgAction::SetTrue)
+            .required(false),
+        Arg::new(PRUNE_ARG)
+            .long(PRUNE_ARG)
+            .help("disable or delete web scenarios, items and triggers which urls are absent in source")
+            .action(ArgAction::SetTrue)
+            .required(false),
+        Arg::new(STRICT_ARG)
+            .long(STRICT_ARG)
+            .help("exit with error code when zabbix hosts weren't found for some urls")
+            .action(ArgAction::SetTrue)
+            .required(false),
+        Arg::new(KEEP_GOING_ARG)
+            .long(KEEP_GOING_ARG)
+            .help("process remaining urls when some of them fail, failed urls are listed in report")
+            .action(ArgAction::SetTrue)
+            .required(false),
+        Arg::new(REPORT_FILE_ARG)
+            .long(REPORT_FILE_ARG)
+            .help("write run report as json to file")
+            .required(false),
+        Arg::new(CONCURRENCY_ARG)
+            .long(CONCURRENCY_ARG)
+            .help("number of hosts processed in parallel, 0 is treated as 1")
+            .value_parser(clap::value_parser!(usize))
+            .default_value(CONCURRENCY_DEFAULT_VALUE)
+            .required(false),
+        Arg::new(RATE_LIMIT_ARG)
+            .long(RATE_LIMIT_ARG)
+            .help("max zabbix api requests per second, 0 - unlimited")
+            .value_parser(clap::value_parser!(u32))
+            .default_value(RATE_LIMIT_DEFAULT_VALUE)
+            .required(false),
+    ]
+}
+
 pub fn init_working_dir(matches: &ArgMatches) {
     let working_directory: &Path =
         get_argument_path_value(&matches, WORK_DIR_ARG, WORK_DIR_DEFAULT_VALUE);
 
-    debug!("working directory '{}'", &working_directory.display());
-
     env::set_current_dir(&working_directory).expect("couldn't set working directory");
 }
 
-fn init_logging(matches: &ArgMatches) {
+/// Logging is initialized once config is loaded, config load errors are printed to stderr.
+/// Working directory is logged here, it's set before logging is initialized.
+/// Returned handle applies reloaded config.
+fn init_logging(matches: &ArgMatches, logging_config: &LoggingConfig) -> OperationResult<Handle> {
+    let logging_config = get_cli_logging_config(matches, logging_config)?;
+
+    let handle = log4rs::init_config(logging_config).context("logging init error")?;
+
+    let working_directory: &Path =
+        get_argument_path_value(matches, WORK_DIR_ARG, WORK_DIR_DEFAULT_VALUE);
+
+    debug!("working directory '{}'", working_directory.display());
+
+    Ok(handle)
+}
+
+fn get_cli_logging_config(matches: &ArgMatches, logging_config: &LoggingConfig) -> OperationResult<Config> {
     let log_level = match matches.get_one::<String>(LOG_LEVEL_ARG) {
         Some(value) => value,
         None => LOG_LEVEL_DEFAULT_VALUE,
     };
 
-    let logging_config = get_logging_config(log_level);
-    log4rs::init_config(logging_config).expect("logging init error");
-}
-
-pub fn process_cli_commands(matches: &ArgMatches) {
-    match matches.subcommand() {
-        Some(("gen", matches)) => {
-            let config_file_path = Path::new("wszl.yml");
-
-            match load_config_from_file(config_file_path) {
-                Ok(config) => {
-                    let http_client = Client::new();
-
-                    let zabbix_client =
-                        ZabbixApiClientImpl::new(http_client, &config.zabbix.api.endpoint);
-
-                    let item_key_search_mask =
-                        matches.get_one::<String>(ITEM_KEY_SEARCH_MASK_ARG).unwrap();
-                    debug!("item key search mask '{item_key_search_mask}'");
-                    let url_source_type = matches.get_one::<String>(SOURCE_ARG).unwrap();
-                    debug!("url source type '{url_source_type}'");
-                    let filename = matches.get_one::<String>(FILE_ARG).unwrap();
-                    debug!("filename '{filename}'");
-
-                    info!("collecting urls from source '{url_source_type}'..");
-
-                    if url_source_type == SOURCE_ARG_DEFAULT_VALUE {
-                        let url_provider = ZabbixUrlSourceProvider::new(
-                            &config.zabbix,
-                            zabbix_client.clone(),
-                            item_key_search_mask,
-                        );
-
-                        match generate_web_scenarios_and_triggers(
-                            &zabbix_client,
-                            &config.zabbix.api.username,
-                            &config.zabbix.api.password,
-                            url_provider,
-                            &config.zabbix.target_hostname,
-                            &config.zabbix.scenario,
-                            &config.zabbix.item,
-                            &config.zabbix.trigger,
-                        ) {
-                            Ok(_) => exit(OK_EXIT_CODE),
-                            Err(e) => {
-                                eprintln!("generation error: {}", e);
-                                error!("{}", e.root_cause());
-                                exit(ERROR_EXIT_CODE)
-                            }
-                        }
-                    } else if url_source_type == SOURCE_ARG_FILE_VALUE {
-                        let url_provider = FileUrlSourceProvider::new(filename);
-
-                        match generate_web_scenarios_and_triggers(
-                            &zabbix_client,
-                            &config.zabbix.api.username,
-                            &config.zabbix.api.password,
-                            url_provider,
-                            &config.zabbix.target_hostname,
-                            &config.zabbix.scenario,
-                            &config.zabbix.item,
-                            &config.zabbix.trigger,
-                        ) {
-                            Ok(_) => exit(OK_EXIT_CODE),
-                            Err(e) => {
-                                eprintln!("generation error: {}", e);
-                                error!("{}", e.root_cause());
-                                exit(ERROR_EXIT_CODE)
-                            }
-                        }
-                    } else {
-                        error!("unsupported data source type '{url_source_type}'");
-                        exit(ERROR_EXIT_CODE)
-                    }
-                }
-                Err(e) => {
-                    error!("config load error: {}", e);
-                    error!("{}", e.root_cause());
-                    exit(ERROR_EXIT_CODE);
-                }
-            }
-        }
+    get_logging_config(log_level, &apply_logging_args(matches, logging_config))
+}
+
+/// CLI options override `logging` config section
+fn apply_logging_args(matches: &ArgMatches, logging_config: &LoggingConfig) -> LoggingConfig {
+    let mut logging_config = logging_config.clone();
+
+    if let Some(target) = matches.get_one::<String>(LOG_TARGET_ARG) {
+        logging_config.target = match target.as_str() {
+            LOG_TARGET_STDOUT_VALUE => LogTarget::Stdout,
+            LOG_TARGET_STDERR_VALUE => LogTarget::Stderr,
+            LOG_TARGET_SYSLOG_VALUE => LogTarget::Syslog,
+            _ => LogTarget::File,
+        };
+    }
+
+    if let Some(file) = matches.get_one::<String>(LOG_FILE_ARG) {
+        logging_config.file = file.to_string();
+    }
+
+    if let Some(format) = matches.get_one::<String>(LOG_FORMAT_ARG) {
+        logging_config.format = match format.as_str() {
+            LOG_FORMAT_JSON_VALUE => LogFormat::Json,
+            _ => LogFormat::Text,
+        };
+    }
+
+    logging_config
+}
+
+pub fn process_cli_commands(root_matches: &ArgMatches) {
+    match root_matches.subcommand() {
+        Some((GENERATE_COMMAND, matches)) => exit_with_result(generate(root_matches, matches)),
+        #[cfg(unix)]
+        Some((DAEMON_COMMAND, matches)) => exit_with_result(run_daemon(root_matches, matches)),
         _ => println!("use -h to get help"),
     }
 }
 
+fn exit_with_result(result: AppResult<()>) {
+    match result {
+        Ok(()) => exit(OK_EXIT_CODE),
+        Err(e) => {
+            eprintln!("{e}");
+            error!("{e}");
+            error!("{}", e.root_cause());
+            exit(get_exit_code(&e))
+        }
+    }
+}
+
+/// Documented in README, keep in sync
+fn get_exit_code(error: &AppError) -> i32 {
+    match error {
+        AppError::Config(_) => CONFIG_ERROR_EXIT_CODE,
+        AppError::Auth(_) => AUTH_ERROR_EXIT_CODE,
+        AppError::Source(_) => SOURCE_ERROR_EXIT_CODE,
+        AppError::PartialFailure { .. } => PARTIAL_FAILURE_EXIT_CODE,
+        AppError::Api(_) => API_ERROR_EXIT_CODE,
+        AppError::Other(_) => ERROR_EXIT_CODE,
+    }
+}
+
+fn generate(root_matches: &ArgMatches, matches: &ArgMatches) -> AppResult<()> {
+    let config = load_cli_config(root_matches)?;
+
+    init_logging(root_matches, &config.logging).map_err(AppError::Config)?;
+
+    let zabbix_client = ZabbixApiClientImpl::new(Client::new(), &config.zabbix.api.endpoint);
+
+    let url_source_type = get_url_source_type(matches)?;
+    let options = get_generate_options(matches, matches.get_flag(DRY_RUN_ARG));
+    let plan_format = matches.get_one::<String>(PLAN_FORMAT_ARG).unwrap();
+    let report_file = matches.get_one::<String>(REPORT_FILE_ARG);
+
+    init_rate_limit(matches);
+
+    let session = login(&zabbix_client, &config.zabbix.api)?;
+
+    let result = generate_web_scenarios_and_triggers(
+        &zabbix_client,
+        &session,
+        get_url_provider(matches, url_source_type, &config, &zabbix_client, &session),
+        &config.zabbix,
+        &options,
+    );
+
+    if let Err(e) = logout(&zabbix_client, &config.zabbix.api, &session) {
+        warn!("{}", e);
+    }
+
+    process_generation_result(result?, &options, plan_format, report_file)
+}
+
+/// Config file or directory from `--config` option, profile from `--profile` option
+fn load_cli_config(root_matches: &ArgMatches) -> AppResult<AppConfig> {
+    let config_file_path = get_argument_path_value(root_matches, CONFIG_ARG, CONFIG_DEFAULT_VALUE);
+    let profile = root_matches.get_one::<String>(PROFILE_ARG).map(|p| p.as_str());
+
+    let config = load_config_from_file(config_file_path, profile)?;
+
+    info!("config has been loaded from '{}'", config_file_path.display());
+
+    Ok(config)
+}
+
+fn get_generate_options(matches: &ArgMatches, dry_run: bool) -> GenerateOptions {
+    GenerateOptions {
+        sync: matches.get_flag(SYNC_ARG),
+        prune: matches.get_flag(PRUNE_ARG),
+        dry_run,
+        strict: matches.get_flag(STRICT_ARG),
+        keep_going: matches.get_flag(KEEP_GOING_ARG),
+        concurrency: *matches.get_one::<usize>(CONCURRENCY_ARG).unwrap(),
+    }
+}
+
+fn get_url_source_type(matches: &ArgMatches) -> AppResult<&str> {
+    let url_source_type = matches.get_one::<String>(SOURCE_ARG).unwrap();
+    debug!("url source type '{url_source_type}'");
+
+    if ![
+        SOURCE_ARG_DEFAULT_VALUE,
+        SOURCE_ARG_FILE_VALUE,
+        SOURCE_ARG_YAML_VALUE,
+        SOURCE_ARG_JSON_VALUE,
+    ]
+    .contains(&url_source_type.as_str())
+    {
+        return Err(AppError::Source(anyhow!("unsupported data source type '{url_source_type}'")));
+    }
+
+    Ok(url_source_type)
+}
+
+fn init_rate_limit(matches: &ArgMatches) {
+    let rate_limit = *matches.get_one::<u32>(RATE_LIMIT_ARG).unwrap();
+
+    if rate_limit > 0 {
+        set_rate_limit(rate_limit);
+    }
+}
+
+fn get_url_provider(
+    matches: &ArgMatches,
+    url_source_type: &str,
+    config: &AppConfig,
+    zabbix_client: &ZabbixApiClientImpl,
+    session: &str,
+) -> Box<dyn UrlSourceProvider> {
+    let item_key_search_mask = matches.get_one::<String>(ITEM_KEY_SEARCH_MASK_ARG).unwrap();
+    debug!("item key search mask '{item_key_search_mask}'");
+    let filename = get_source_filename(matches, url_source_type);
+    debug!("filename '{filename}'");
+
+    info!("collecting urls from source '{url_source_type}'..");
+
+    match url_source_type {
+        SOURCE_ARG_FILE_VALUE => Box::new(FileUrlSourceProvider::new(filename)),
+        SOURCE_ARG_YAML_VALUE => Box::new(StructuredUrlSourceProvider::new(
+            filename,
+            StructuredFormat::Yaml,
+        )),
+        SOURCE_ARG_JSON_VALUE => Box::new(StructuredUrlSourceProvider::new(
+            filename,
+            StructuredFormat::Json,
+        )),
+        _ => Box::new(ZabbixUrlSourceProvider::new(
+            &config.zabbix,
+            zabbix_client.clone(),
+            session,
+            item_key_search_mask,
+        )),
+    }
+}
+
+fn process_generation_result(
+    report: Report,
+    options: &GenerateOptions,
+    plan_format: &str,
+    report_file: Option<&String>,
+) -> AppResult<()> {
+    let json_plan = options.dry_run && plan_format == PLAN_FORMAT_JSON_VALUE;
+
+    if options.dry_run {
+        if json_plan {
+            let json = report.plan.to_json().context("plan serialization error")?;
+            println!("{json}");
+        } else {
+            println!("{}", report.plan);
+        }
+    }
+
+    // Keep json plan on stdout valid
+    if json_plan {
+        eprint!("{report}");
+    } else {
+        print!("{report}");
+    }
+
+    if let Some(report_file) = report_file {
+        write_report(&report, report_file)
+            .context(format!("unable to write report file '{report_file}'"))?;
+    }
+
+    match report.get_error(options.strict) {
+        Some(e) => Err(e),
+        None => Ok(()),
+    }
+}
+
+fn write_report(report: &Report, filename: &str) -> EmptyResult {
+    let json = report.to_json()?;
+    fs::write(filename, json)?;
+    info!("report has been written to '{filename}'");
+    Ok(())
+}
+
+/// Default file name depends on source type: `urls.txt`, `urls.yml` or `urls.json`
+fn get_source_filename<'a>(matches: &'a ArgMatches, url_source_type: &str) -> &'a str {
+    let filename = matches.get_one::<String>(FILE_ARG).unwrap();
+
+    if matches.value_source(FILE_ARG) != Some(ValueSource::DefaultValue) {
+        return filename;
+    }
+
+    match url_source_type {
+        SOURCE_ARG_YAML_VALUE => FILE_ARG_YAML_DEFAULT_VALUE,
+        SOURCE_ARG_JSON_VALUE => FILE_ARG_JSON_DEFAULT_VALUE,
+        _ => filename,
+    }
+}
+
 fn get_argument_path_value<'a>(
     matches: &'a ArgMatches,
     long_argument: &str,
@@ -0,0 +1,87 @@
+use std::io;
+use std::mem;
+use std::ptr;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use anyhow::Context;
+
+use crate::types::EmptyResult;
+
+/// How often `wait_for_signal` checks requests
+const SIGNAL_CHECK_INTERVAL: Duration = Duration::from_millis(200);
+
+static RELOAD_REQUESTED: AtomicBool = AtomicBool::new(false);
+static TERMINATE_REQUESTED: AtomicBool = AtomicBool::new(false);
+
+/// SIGHUP requests config reload, SIGTERM and SIGINT request shutdown.
+/// Handlers only set flags, requests are processed between runs.
+pub fn install_signal_handlers() -> EmptyResult {
+    for signal in [libc::SIGHUP, libc::SIGTERM, libc::SIGINT] {
+        let mut action: libc::sigaction = unsafe { mem::zeroed() };
+        action.sa_sigaction = handle_signal as extern "C" fn(libc::c_int) as libc::sighandler_t;
+        // Interrupted syscalls (i.e. http requests) are restarted, run is finished normally
+        action.sa_flags = libc::SA_RESTART;
+
+        let result = unsafe {
+            libc::sigemptyset(&mut action.sa_mask);
+            libc::sigaction(signal, &action, ptr::null_mut())
+        };
+
+        if result != 0 {
+            return Err(io::Error::last_os_error())
+                .context(format!("unable to install handler of signal {signal}"));
+        }
+    }
+
+    Ok(())
+}
+
+extern "C" fn handle_signal(signal: libc::c_int) {
+    match signal {
+        libc::SIGHUP => RELOAD_REQUESTED.store(true, Ordering::SeqCst),
+        _ => TERMINATE_REQUESTED.store(true, Ordering::SeqCst),
+    }
+}
+
+pub fn is_terminate_requested() -> bool {
+    TERMINATE_REQUESTED.load(Ordering::SeqCst)
+}
+
+/// Returns reload request and resets it
+pub fn take_reload_request() -> bool {
+    RELOAD_REQUESTED.swap(false, Ordering::SeqCst)
+}
+
+/// Sleep until timeout expires, returns earlier on reload or shutdown request
+pub fn wait_for_signal(timeout: Duration) {
+    let started = Instant::now();
+
+    while started.elapsed() < timeout {
+        if is_terminate_requested() || RELOAD_REQUESTED.load(Ordering::SeqCst) {
+            return;
+        }
+
+        thread::sleep(SIGNAL_CHECK_INTERVAL.min(timeout.saturating_sub(started.elapsed())));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, Instant};
+
+    use crate::cli::signals::{handle_signal, take_reload_request, wait_for_signal};
+
+    #[test]
+    fn sighup_should_interrupt_wait_and_request_reload() {
+        handle_signal(libc::SIGHUP);
+
+        let started = Instant::now();
+        wait_for_signal(Duration::from_secs(30));
+
+        assert!(started.elapsed() < Duration::from_secs(5));
+        assert!(take_reload_request());
+        assert!(!take_reload_request());
+    }
+}
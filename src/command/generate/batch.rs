@@ -0,0 +1,353 @@
+use std::slice;
+
+use anyhow::Context;
+use serde_json::{Map, Value};
+use zabbix_api::client::client::ZabbixApiClient;
+
+use crate::command::generate::plan::{ObjectAction, ObjectType};
+use crate::command::generate::report::UrlFailure;
+use crate::types::{EmptyResult, OperationResult};
+use crate::zabbix::diff::get_field_names;
+use crate::zabbix::item::{create_items, update_item, CreateItemRequest};
+use crate::zabbix::trigger::{create_triggers, update_trigger, CreateTriggerRequest};
+use crate::zabbix::webscenario::{
+    create_web_scenarios, update_web_scenario, CreateWebScenarioRequest,
+};
+
+/// Changes of a single host. Missing objects of each type are created with a single request.
+///
+/// Items go first, then web scenarios and triggers, because trigger expressions refer to them.
+#[derive(Default)]
+pub struct HostChanges {
+    pub items: ObjectChanges<CreateItemRequest>,
+    pub web_scenarios: ObjectChanges<CreateWebScenarioRequest>,
+    pub triggers: ObjectChanges<CreateTriggerRequest>,
+}
+
+/// Outcome of applied host changes
+#[derive(Default, Debug)]
+pub struct AppliedChanges {
+    /// Log messages about performed changes
+    pub messages: Vec<String>,
+
+    /// Objects which weren't created or updated
+    pub failed_objects: Vec<(ObjectType, String)>,
+
+    /// One per failed url
+    pub failures: Vec<UrlFailure>,
+}
+
+impl AppliedChanges {
+    fn is_url_failed(&self, url: &str) -> bool {
+        self.failures.iter().any(|failure| failure.url == url)
+    }
+
+    fn add_failure(&mut self, host: &str, url: &str, error: &anyhow::Error) {
+        if !self.is_url_failed(url) {
+            self.failures.push(UrlFailure::new(host, url, error));
+        }
+    }
+}
+
+impl HostChanges {
+    /// Stops at the first failed request. With `keep_going` failed batch is retried object
+    /// by object, so only objects of the failed urls are left behind. Remaining objects
+    /// of the failed url are skipped, i.e. trigger of the item which wasn't created.
+    ///
+    /// Objects of urls which are already in `applied` failures are skipped as well.
+    pub fn apply(
+        self,
+        zabbix_client: &impl ZabbixApiClient,
+        session: &str,
+        host: &str,
+        keep_going: bool,
+        applied: &mut AppliedChanges,
+    ) {
+        self.items.apply(
+            ObjectType::Item,
+            host,
+            keep_going,
+            |requests| create_items(zabbix_client, session, requests),
+            |id, changes| update_item(zabbix_client, session, id, changes),
+            applied,
+        );
+
+        self.web_scenarios.apply(
+            ObjectType::WebScenario,
+            host,
+            keep_going,
+            |requests| create_web_scenarios(zabbix_client, session, requests),
+            |id, changes| update_web_scenario(zabbix_client, session, id, changes),
+            applied,
+        );
+
+        self.triggers.apply(
+            ObjectType::Trigger,
+            host,
+            keep_going,
+            |requests| create_triggers(zabbix_client, session, requests),
+            |id, changes| update_trigger(zabbix_client, session, id, changes),
+            applied,
+        );
+    }
+}
+
+/// Objects of the same type to create and update. Object is identified by name,
+/// i.e. item key or trigger description.
+pub struct ObjectChanges<R> {
+    creates: Vec<PendingCreate<R>>,
+    updates: Vec<PendingUpdate>,
+}
+
+struct PendingCreate<R> {
+    name: String,
+    url: String,
+    request: R,
+}
+
+struct PendingUpdate {
+    name: String,
+    url: String,
+    id: String,
+    changes: Map<String, Value>,
+}
+
+impl<R> Default for ObjectChanges<R> {
+    fn default() -> Self {
+        ObjectChanges {
+            creates: vec![],
+            updates: vec![],
+        }
+    }
+}
+
+impl<R: Clone> ObjectChanges<R> {
+    pub fn add(&mut self, name: &str, url: &str, request: &R, action: &ObjectAction) {
+        match action {
+            ObjectAction::Create => self.creates.push(PendingCreate {
+                name: name.to_string(),
+                url: url.to_string(),
+                request: request.clone(),
+            }),
+            ObjectAction::Update { id, changes } => self.updates.push(PendingUpdate {
+                name: name.to_string(),
+                url: url.to_string(),
+                id: id.to_string(),
+                changes: changes.clone(),
+            }),
+            ObjectAction::Skip => {}
+        }
+    }
+
+    /// Object is already planned to be created or updated, i.e. url is duplicated in url source
+    pub fn contains(&self, name: &str) -> bool {
+        self.creates.iter().any(|pending| pending.name == name)
+            || self.updates.iter().any(|pending| pending.name == name)
+    }
+
+    fn apply(
+        self,
+        object_type: ObjectType,
+        host: &str,
+        keep_going: bool,
+        mut create: impl FnMut(&[R]) -> OperationResult<Vec<String>>,
+        mut update: impl FnMut(&str, &Map<String, Value>) -> EmptyResult,
+        applied: &mut AppliedChanges,
+    ) {
+        let mut creates: Vec<PendingCreate<R>> = vec![];
+
+        for pending in self.creates {
+            if is_stopped(applied, keep_going) || applied.is_url_failed(&pending.url) {
+                applied.failed_objects.push((object_type, pending.name));
+            } else {
+                creates.push(pending);
+            }
+        }
+
+        if !creates.is_empty() {
+            let requests: Vec<R> = creates.iter().map(|pending| pending.request.clone()).collect();
+
+            match create(&requests) {
+                Ok(_) => {
+                    for pending in creates {
+                        applied.messages.push(format!("{object_type} '{}' has been created", pending.name));
+                    }
+                }
+                Err(e) if keep_going && creates.len() > 1 => {
+                    applied.messages.push(format!(
+                        "unable to create {} {object_type}s with a single request, create one by one: {}",
+                        creates.len(), e.root_cause()
+                    ));
+
+                    for pending in creates {
+                        if applied.is_url_failed(&pending.url) {
+                            applied.failed_objects.push((object_type, pending.name));
+                            continue;
+                        }
+
+                        match create(slice::from_ref(&pending.request)) {
+                            Ok(_) => applied.messages.push(format!("{object_type} '{}' has been created", pending.name)),
+                            Err(e) => {
+                                let e = e.context(format!("unable to create {object_type} '{}'", pending.name));
+                                applied.add_failure(host, &pending.url, &e);
+                                applied.failed_objects.push((object_type, pending.name));
+                            }
+                        }
+                    }
+                }
+                Err(e) => {
+                    let names: Vec<&str> = creates.iter().map(|pending| pending.name.as_str()).collect();
+                    let e = e.context(format!("unable to create {object_type}s: {}", names.join(", ")));
+
+                    for pending in creates {
+                        applied.add_failure(host, &pending.url, &e);
+                        applied.failed_objects.push((object_type, pending.name));
+                    }
+                }
+            }
+        }
+
+        for pending in self.updates {
+            if is_stopped(applied, keep_going) || applied.is_url_failed(&pending.url) {
+                applied.failed_objects.push((object_type, pending.name));
+                continue;
+            }
+
+            match update(&pending.id, &pending.changes)
+                .context(format!("unable to update {object_type} '{}'", pending.name)) {
+                Ok(()) => applied.messages.push(format!(
+                    "{object_type} '{}' has been updated: {}", pending.name, get_field_names(&pending.changes)
+                )),
+                Err(e) => {
+                    applied.add_failure(host, &pending.url, &e);
+                    applied.failed_objects.push((object_type, pending.name));
+                }
+            }
+        }
+    }
+}
+
+/// Without `keep_going` nothing is applied after the first failure
+fn is_stopped(applied: &AppliedChanges, keep_going: bool) -> bool {
+    !keep_going && !applied.failures.is_empty()
+}
+
+#[cfg(test)]
+mod tests {
+    use anyhow::anyhow;
+    use serde_json::Map;
+
+    use crate::command::generate::batch::{AppliedChanges, ObjectChanges};
+    use crate::command::generate::plan::{ObjectAction, ObjectType};
+
+    fn get_changes(objects: &[(&str, &str)]) -> ObjectChanges<String> {
+        let mut changes: ObjectChanges<String> = ObjectChanges::default();
+
+        for (name, url) in objects {
+            changes.add(name, url, &name.to_string(), &ObjectAction::Create);
+        }
+
+        changes
+    }
+
+    #[test]
+    fn planned_objects_should_be_found_by_name() {
+        let mut changes = get_changes(&[("a", "https://a.com")]);
+
+        changes.add("b", "https://b.com", &"b".to_string(), &ObjectAction::Update { id: "1".to_string(), changes: Map::new() });
+        changes.add("c", "https://c.com", &"c".to_string(), &ObjectAction::Skip);
+
+        assert!(changes.contains("a"));
+        assert!(changes.contains("b"));
+        assert!(!changes.contains("c"));
+    }
+
+    #[test]
+    fn objects_should_be_created_with_single_request() {
+        let changes = get_changes(&[("a", "https://a.com"), ("b", "https://b.com")]);
+
+        let mut create_requests: Vec<Vec<String>> = vec![];
+        let mut applied = AppliedChanges::default();
+
+        changes.apply(
+            ObjectType::Item,
+            "srv1",
+            false,
+            |batch| {
+                create_requests.push(batch.to_vec());
+                Ok(vec!["1".to_string(), "2".to_string()])
+            },
+            |_, _| Ok(()),
+            &mut applied,
+        );
+
+        assert_eq!(create_requests, vec![vec!["a".to_string(), "b".to_string()]]);
+        assert_eq!(applied.messages, vec!["item 'a' has been created", "item 'b' has been created"]);
+        assert!(applied.failures.is_empty());
+    }
+
+    #[test]
+    fn failed_batch_should_fail_all_objects_without_keep_going() {
+        let changes = get_changes(&[("a", "https://a.com"), ("b", "https://b.com")]);
+
+        let mut applied = AppliedChanges::default();
+
+        changes.apply(
+            ObjectType::Trigger,
+            "srv1",
+            false,
+            |_| Err(anyhow!("invalid expression")),
+            |_, _| Ok(()),
+            &mut applied,
+        );
+
+        assert_eq!(applied.failed_objects.len(), 2);
+        assert_eq!(applied.failures.len(), 2);
+        assert_eq!(applied.failures[0].root_cause, "invalid expression");
+
+        // Nothing is applied after the first failure
+        let mut requests = 0;
+
+        get_changes(&[("c", "https://c.com")]).apply(
+            ObjectType::Trigger,
+            "srv1",
+            false,
+            |_| {
+                requests += 1;
+                Ok(vec![])
+            },
+            |_, _| Ok(()),
+            &mut applied,
+        );
+
+        assert_eq!(requests, 0);
+        assert_eq!(applied.failed_objects.len(), 3);
+    }
+
+    #[test]
+    fn failed_batch_should_be_retried_one_by_one_with_keep_going() {
+        let changes = get_changes(&[("a", "https://a.com"), ("b", "https://b.com"), ("c", "https://c.com")]);
+
+        let mut applied = AppliedChanges::default();
+
+        changes.apply(
+            ObjectType::Trigger,
+            "srv1",
+            true,
+            |batch| match batch.iter().any(|name| name == "b") {
+                true => Err(anyhow!("invalid expression")),
+                false => Ok(vec!["1".to_string()]),
+            },
+            |_, _| Ok(()),
+            &mut applied,
+        );
+
+        assert_eq!(applied.failed_objects, vec![(ObjectType::Trigger, "b".to_string())]);
+        assert_eq!(applied.failures.len(), 1);
+        assert_eq!(applied.failures[0].url, "https://b.com");
+        assert_eq!(applied.failures[0].host, "srv1");
+        assert_eq!(applied.failures[0].root_cause, "invalid expression");
+        assert!(applied.messages.contains(&"trigger 'a' has been created".to_string()));
+        assert!(applied.messages.contains(&"trigger 'c' has been created".to_string()));
+    }
+}
@@ -0,0 +1,80 @@
+use std::collections::HashMap;
+use std::sync::{Mutex, MutexGuard};
+
+use anyhow::{anyhow, Context};
+use zabbix_api::client::client::ZabbixApiClient;
+
+use crate::config::trigger::TriggerDependencyConfig;
+use crate::template::process_template_string;
+use crate::types::OperationResult;
+use crate::zabbix::host::HostIdCache;
+use crate::zabbix::trigger::{find_trigger, TriggerDependency};
+
+/// Resolves `trigger.dependencies` to trigger ids. Resolved ids are cached for the run,
+/// because the same dependency is usually shared by all urls of the host.
+#[derive(Default)]
+pub struct TriggerDependencyResolver {
+    trigger_ids: Mutex<HashMap<(String, String), String>>,
+}
+
+impl TriggerDependencyResolver {
+    pub fn resolve(
+        &self,
+        zabbix_client: &impl ZabbixApiClient,
+        session: &str,
+        host_cache: &HostIdCache,
+        dependency_configs: &[TriggerDependencyConfig],
+        template_vars: &HashMap<String, String>,
+    ) -> OperationResult<Vec<TriggerDependency>> {
+        let mut dependencies: Vec<TriggerDependency> = vec![];
+
+        for dependency_config in dependency_configs {
+            let host = process_template_string(&dependency_config.host, template_vars);
+            let description = process_template_string(&dependency_config.description, template_vars);
+
+            let key = (host.to_string(), description.to_string());
+
+            let cached_trigger_id = self.lock_trigger_ids().get(&key).cloned();
+
+            let trigger_id = match cached_trigger_id {
+                Some(trigger_id) => trigger_id,
+                None => {
+                    let trigger_id =
+                        find_dependency_trigger_id(zabbix_client, session, host_cache, &host, &description)
+                            .context(format!(
+                                "unable to resolve trigger dependency '{description}' on host '{host}'"
+                            ))?;
+
+                    self.lock_trigger_ids().insert(key, trigger_id.to_string());
+                    trigger_id
+                }
+            };
+
+            dependencies.push(TriggerDependency { trigger_id });
+        }
+
+        Ok(dependencies)
+    }
+
+    fn lock_trigger_ids(&self) -> MutexGuard<'_, HashMap<(String, String), String>> {
+        self.trigger_ids.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+fn find_dependency_trigger_id(
+    zabbix_client: &impl ZabbixApiClient,
+    session: &str,
+    host_cache: &HostIdCache,
+    host: &str,
+    description: &str,
+) -> OperationResult<String> {
+    let host_id = host_cache.find_host_id(zabbix_client, session, host)?
+        .ok_or_else(|| anyhow!("zabbix host wasn't found"))?;
+
+    let trigger = find_trigger(zabbix_client, session, &host_id, description)?
+        .ok_or_else(|| anyhow!("trigger wasn't found"))?;
+
+    debug!("trigger dependency '{description}' on host '{host}' resolved to id {}", trigger.trigger_id);
+
+    Ok(trigger.trigger_id)
+}
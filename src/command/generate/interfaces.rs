@@ -0,0 +1,149 @@
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+use anyhow::anyhow;
+use zabbix_api::client::client::ZabbixApiClient;
+
+use crate::config::item::{InterfaceMode, ZabbixItemConfig};
+use crate::types::OperationResult;
+use crate::zabbix::interface::{find_host_interfaces, ZabbixHostInterface};
+
+/// Items of these types don't use host interface, i.e. Zabbix agent (active), trapper, calculated
+const NO_INTERFACE_ID: &str = "0";
+
+const MAIN_INTERFACE: &str = "1";
+
+/// Resolves item interface id for `interface: auto` mode.
+/// Host interfaces are requested once per host during the run.
+#[derive(Default)]
+pub struct InterfaceResolver {
+    host_interfaces: Mutex<HashMap<String, Vec<ZabbixHostInterface>>>,
+}
+
+impl InterfaceResolver {
+    pub fn resolve(
+        &self,
+        zabbix_client: &impl ZabbixApiClient,
+        session: &str,
+        host: &str,
+        host_id: &str,
+        item_config: &ZabbixItemConfig,
+    ) -> OperationResult<String> {
+        if item_config.interface == InterfaceMode::Fixed {
+            return Ok(item_config.interface_id.to_string());
+        }
+
+        let cached_interfaces = self
+            .host_interfaces
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .get(host_id)
+            .cloned();
+
+        let interfaces = match cached_interfaces {
+            Some(interfaces) => interfaces,
+            None => {
+                let interfaces = find_host_interfaces(zabbix_client, session, host_id)?;
+                debug!("host '{host}' interfaces: {:?}", interfaces);
+
+                self.host_interfaces
+                    .lock()
+                    .unwrap_or_else(|e| e.into_inner())
+                    .insert(host_id.to_string(), interfaces.clone());
+
+                interfaces
+            }
+        };
+
+        select_interface_id(&interfaces, item_config.r#type).ok_or_else(|| {
+            anyhow!(
+                "host '{host}' has no main {} interface for item type {}",
+                get_interface_types(item_config.r#type)
+                    .unwrap_or_default()
+                    .iter()
+                    .map(|interface_type| get_interface_type_name(interface_type))
+                    .collect::<Vec<&str>>()
+                    .join(" or "),
+                item_config.r#type
+            )
+        })
+    }
+}
+
+/// Zabbix API: https://www.zabbix.com/documentation/6.0/en/manual/api/reference/item/object#item
+///
+/// Returns interface types suitable for item type or `None` when item doesn't use interface
+fn get_interface_types(item_type: u8) -> Option<Vec<&'static str>> {
+    match item_type {
+        // Zabbix agent
+        0 => Some(vec!["1"]),
+        // SNMP agent
+        20 => Some(vec!["2"]),
+        // IPMI agent
+        12 => Some(vec!["3"]),
+        // JMX agent
+        16 => Some(vec!["4"]),
+        // Simple check, external check
+        3 | 10 => Some(vec!["1", "2", "3", "4"]),
+        _ => None,
+    }
+}
+
+fn get_interface_type_name(interface_type: &str) -> &str {
+    match interface_type {
+        "1" => "agent",
+        "2" => "SNMP",
+        "3" => "IPMI",
+        "4" => "JMX",
+        _ => interface_type,
+    }
+}
+
+/// Main interface of the first suitable type
+fn select_interface_id(interfaces: &[ZabbixHostInterface], item_type: u8) -> Option<String> {
+    let interface_types = match get_interface_types(item_type) {
+        Some(interface_types) => interface_types,
+        None => return Some(NO_INTERFACE_ID.to_string()),
+    };
+
+    interface_types.iter().find_map(|interface_type| {
+        interfaces
+            .iter()
+            .find(|interface| &interface.r#type == interface_type && interface.main == MAIN_INTERFACE)
+            .map(|interface| interface.interface_id.to_string())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::command::generate::interfaces::select_interface_id;
+    use crate::zabbix::interface::ZabbixHostInterface;
+
+    fn get_interface(interface_id: &str, r#type: &str, main: &str) -> ZabbixHostInterface {
+        ZabbixHostInterface {
+            interface_id: interface_id.to_string(),
+            r#type: r#type.to_string(),
+            main: main.to_string(),
+        }
+    }
+
+    #[test]
+    fn main_interface_of_item_type_should_be_selected() {
+        let interfaces = vec![
+            get_interface("1", "2", "1"),
+            get_interface("2", "1", "0"),
+            get_interface("3", "1", "1"),
+        ];
+
+        assert_eq!(select_interface_id(&interfaces, 0), Some("3".to_string()));
+        assert_eq!(select_interface_id(&interfaces, 20), Some("1".to_string()));
+        assert_eq!(select_interface_id(&interfaces, 3), Some("3".to_string()));
+        assert_eq!(select_interface_id(&interfaces, 12), None);
+        assert_eq!(select_interface_id(&[], 0), None);
+    }
+
+    #[test]
+    fn interface_should_not_be_required_for_active_agent_items() {
+        assert_eq!(select_interface_id(&[], 7), Some("0".to_string()));
+    }
+}
@@ -1,188 +1,703 @@
+use std::collections::HashMap;
+use std::mem;
+use std::sync::atomic::{AtomicBool, Ordering};
+
 use anyhow::Context;
+use log::Level;
+use serde::Serialize;
 use zabbix_api::client::client::ZabbixApiClient;
-use zabbix_api::item::create::CreateItemRequest;
-use zabbix_api::item::get::GetItemsRequestByKey;
-use zabbix_api::trigger::create::CreateTriggerRequest;
-use zabbix_api::trigger::get::GetTriggerByDescriptionRequest;
-use zabbix_api::webscenario::create::CreateWebScenarioRequest;
-use zabbix_api::webscenario::get::GetWebScenarioByNameRequest;
-use zabbix_api::webscenario::model::ZabbixWebScenarioStep;
-
-use crate::config::item::ZabbixItemConfig;
-use crate::config::trigger::ZabbixTriggerConfig;
-use crate::config::ws::WebScenarioConfig;
-use crate::source::UrlSourceProvider;
-use crate::template::{get_template_vars, process_template_string};
-use crate::types::EmptyResult;
-use crate::zabbix::host::find_zabbix_host_id;
 
+use crate::command::generate::batch::{AppliedChanges, HostChanges};
+use crate::command::generate::dependencies::TriggerDependencyResolver;
+use crate::command::generate::interfaces::InterfaceResolver;
+use crate::command::generate::managed::{get_managed_tags, get_url_hash, URL_HASH_TAG};
+use crate::command::generate::plan::{ObjectAction, ObjectType, Plan};
+use crate::command::generate::prune::prune_orphaned_objects;
+use crate::command::generate::report::{Report, UnresolvedUrl, UrlFailure};
+use crate::command::generate::requests::{
+    get_item_request, get_trigger_request, get_web_scenario_request,
+};
+use crate::command::generate::worker::process_ordered;
+use crate::command::generate::GenerateOptions;
+use crate::config::ZabbixConfig;
+use crate::logging::{clear_log_context, set_log_context};
+use crate::source::{UrlSource, UrlSourceProvider, UrlSources};
+use crate::template::{get_template_vars, SCENARIO_NAME_TEMPLATE_VAR};
+use crate::types::{AppError, AppResult, EmptyResult, OperationResult};
+use crate::zabbix::diff::get_changed_fields;
+use crate::zabbix::host::HostIdCache;
+use crate::zabbix::item::{find_items, CreateItemRequest, ZabbixItem};
+use crate::zabbix::trigger::{find_triggers, CreateTriggerRequest, ZabbixTrigger};
+use crate::zabbix::webscenario::{
+    find_web_scenarios, find_web_scenarios_by_tag_values, CreateWebScenarioRequest, ZabbixWebScenario,
+};
+
+/// Returns report with plan of performed actions. With `dry_run` option nothing is changed in Zabbix.
+///
+/// Url sources are grouped by host. Host ids are resolved with a single request, existing objects
+/// are fetched and missing ones are created with a few requests per host. Hosts are processed
+/// by `options.concurrency` workers, log output of each host is written at once and in the url
+/// source order.
+///
+/// Failed urls are collected in report. Generation stops at the first failed url
+/// unless `options.keep_going` is set, hosts which were processed in parallel are reported as well.
 pub fn generate_web_scenarios_and_triggers(
-    zabbix_client: &impl ZabbixApiClient,
-    zabbix_login: &str,
-    zabbix_password: &str,
+    zabbix_client: &(impl ZabbixApiClient + Sync),
+    session: &str,
     url_source_provider: impl UrlSourceProvider,
-    target_hostname: &str,
-    web_scenario_config: &WebScenarioConfig,
-    item_config: &ZabbixItemConfig,
-    trigger_config: &ZabbixTriggerConfig,
-) -> EmptyResult {
+    zabbix_config: &ZabbixConfig,
+    options: &GenerateOptions,
+) -> AppResult<Report> {
     info!("generate web scenarios and triggers..");
+    debug!("options: {:?}", options);
+
+    let mut report = Report::default();
+
+    let sources = url_source_provider
+        .get_url_sources()
+        .map_err(|e| AppError::unless_transport(e, AppError::Source))?;
+
+    debug!("url sources: {:?}", sources.url_sources);
 
-    let url_sources = url_source_provider.get_url_sources()?;
+    // Prune goes first, so restored web scenarios keep their tags in sync mode
+    if options.prune {
+        let mut plan = Plan::default();
 
-    debug!("url sources: {:?}", url_sources);
+        prune_orphaned_objects(
+            zabbix_client,
+            session,
+            &sources,
+            url_source_provider.get_source_type(),
+            zabbix_config,
+            options,
+            &mut plan,
+        )?;
 
-    let session = zabbix_client.get_auth_session(&zabbix_login, &zabbix_password)?;
+        report.add_plan(plan, &[]);
+    }
+
+    let UrlSources { url_sources, invalid_urls } = sources;
+
+    report.invalid = invalid_urls;
 
-    let mut host_id: String = String::new();
+    let mut host_url_sources = group_by_host(&url_sources, &zabbix_config.target_hostname);
 
-    if !target_hostname.is_empty() {
-        if let Some(id) = find_zabbix_host_id(zabbix_client, &session, &target_hostname)? {
-            host_id = id;
+    let host_cache = HostIdCache::default();
+
+    // Host ids from the source belong to source hosts, not to the target host
+    if zabbix_config.target_hostname.is_empty() {
+        for url_source in &url_sources {
+            if let Some(host_id) = &url_source.host_id {
+                host_cache.insert(&url_source.zabbix_host, host_id);
+            }
         }
+    }
+
+    let hostnames: Vec<String> = host_url_sources.iter().map(|h| h.host.to_string()).collect();
+    let host_ids = host_cache.find_host_ids(zabbix_client, session, &hostnames)?;
+
+    resolve_hosts(&mut host_url_sources, &host_ids);
+
+    let context = GenerationContext {
+        zabbix_client,
+        session,
+        zabbix_config,
+        options,
+        source_type: url_source_provider.get_source_type(),
+        host_cache,
+        dependency_resolver: TriggerDependencyResolver::default(),
+        interface_resolver: InterfaceResolver::default(),
     };
 
-    for url_source in url_sources {
-        debug!("url source: {:?}", url_source);
+    // Without `keep_going` hosts which aren't started yet are skipped after the first failure.
+    // Hosts which were processed in parallel are reported anyway, their changes are applied already
+    let failed = AtomicBool::new(false);
+
+    process_ordered(
+        &host_url_sources,
+        options.concurrency,
+        |host_url_sources| {
+            let mut output = GenerationOutput {
+                host: host_url_sources.host.to_string(),
+                ..Default::default()
+            };
+
+            if failed.load(Ordering::SeqCst) {
+                output.log(Level::Warn, format!("generation has been stopped after failure, skip host '{}'", output.host));
+                return output;
+            }
 
-        let mut zabbix_host: String = url_source.zabbix_host.to_string();
+            // Direct log records of the worker, i.e. Zabbix API calls
+            set_log_context(&output.host, None);
+            process_host(&context, host_url_sources, &mut output);
+            clear_log_context();
 
-        if target_hostname.is_empty() {
-            if let Some(id) = find_zabbix_host_id(zabbix_client, &session, &url_source.zabbix_host)?
-            {
-                host_id = id;
+            if !options.keep_going && !output.failures.is_empty() {
+                failed.store(true, Ordering::SeqCst);
             }
+
+            output
+        },
+        |_, output| {
+            output.write_log();
+            report.add_plan(output.plan, &output.failed_objects);
+            report.unresolved.extend(output.unresolved);
+
+            let failed = !output.failures.is_empty();
+            report.failures.extend(output.failures);
+
+            options.keep_going || !failed
+        },
+    );
+
+    Ok(report)
+}
+
+/// Shared between workers
+struct GenerationContext<'a, C: ZabbixApiClient> {
+    zabbix_client: &'a C,
+    session: &'a str,
+    zabbix_config: &'a ZabbixConfig,
+    options: &'a GenerateOptions,
+    source_type: &'a str,
+    host_cache: HostIdCache,
+    dependency_resolver: TriggerDependencyResolver,
+    interface_resolver: InterfaceResolver,
+}
+
+/// Plan, unresolved and failed urls, log messages of a single host. Messages are buffered,
+/// so output of parallel workers isn't interleaved.
+#[derive(Default)]
+struct GenerationOutput {
+    host: String,
+    plan: Plan,
+    unresolved: Vec<UnresolvedUrl>,
+    failures: Vec<UrlFailure>,
+    failed_objects: Vec<(ObjectType, String)>,
+
+    /// Url which is processed, added to log messages
+    url: Option<String>,
+    messages: Vec<LogMessage>,
+}
+
+/// Host and url are written as fields of JSON log records
+struct LogMessage {
+    level: Level,
+    url: Option<String>,
+    message: String,
+}
+
+impl GenerationOutput {
+    fn fail(&mut self, host: &str, url: &str, error: &anyhow::Error) {
+        self.log_url(Level::Error, Some(url), format!("url '{url}' failed: {error}"));
+        self.log_url(Level::Error, Some(url), error.root_cause().to_string());
+        self.failures.push(UrlFailure::new(host, url, error));
+    }
+
+    fn set_url(&mut self, url: Option<&str>) {
+        self.url = url.map(|url| url.to_string());
+    }
+
+    fn log(&mut self, level: Level, message: String) {
+        let url = self.url.clone();
+        self.messages.push(LogMessage { level, url, message });
+    }
+
+    fn log_url(&mut self, level: Level, url: Option<&str>, message: String) {
+        let url = url.map(|url| url.to_string());
+        self.messages.push(LogMessage { level, url, message });
+    }
+
+    fn write_log(&self) {
+        for message in &self.messages {
+            set_log_context(&self.host, message.url.as_deref());
+            log!(message.level, "{}", message.message);
+        }
+
+        clear_log_context();
+    }
+}
+
+/// Url sources of a single Zabbix host
+#[derive(Debug)]
+struct HostUrlSources<'a> {
+    host: String,
+    status: HostStatus,
+    url_sources: Vec<&'a UrlSource>,
+}
+
+/// Host resolution status, shared by all urls of the host.
+/// Objects are generated only for urls of resolved hosts.
+#[derive(PartialEq, Clone, Debug)]
+enum HostStatus {
+    Resolved { host_id: String },
+    Unresolved,
+}
+
+/// Groups keep the order of the first url of each host. All urls belong to `target_hostname`
+/// when it's set.
+fn group_by_host<'a>(url_sources: &'a [UrlSource], target_hostname: &str) -> Vec<HostUrlSources<'a>> {
+    let mut results: Vec<HostUrlSources> = vec![];
+
+    for url_source in url_sources {
+        let host = if target_hostname.is_empty() {
+            &url_source.zabbix_host
         } else {
-            zabbix_host = target_hostname.to_string();
+            target_hostname
+        };
+
+        match results.iter_mut().find(|group| group.host == host) {
+            Some(group) => group.url_sources.push(url_source),
+            None => results.push(HostUrlSources {
+                host: host.to_string(),
+                status: HostStatus::Unresolved,
+                url_sources: vec![url_source],
+            }),
         }
+    }
 
-        debug!("target hostname '{zabbix_host}'");
-        debug!("target host id '{host_id}'");
+    results
+}
 
-        let template_vars = get_template_vars(&zabbix_host, &url_source.url);
-        debug!("template vars: {:?}", template_vars);
+/// `host_ids`: hostname -> host id, hosts which weren't found are absent
+fn resolve_hosts(host_url_sources: &mut [HostUrlSources], host_ids: &HashMap<String, String>) {
+    for group in host_url_sources {
+        group.status = match host_ids.get(&group.host) {
+            Some(host_id) => HostStatus::Resolved { host_id: host_id.to_string() },
+            None => HostStatus::Unresolved,
+        };
+    }
+}
 
-        if !host_id.is_empty() {
-            let item_key = process_template_string(&item_config.key_template, &template_vars);
+/// Desired objects of a single url
+struct UrlObjects<'a> {
+    url_source: &'a UrlSource,
+    items: Vec<CreateItemRequest>,
+    web_scenario: CreateWebScenarioRequest,
+    triggers: Vec<CreateTriggerRequest>,
+}
+
+/// Existing objects of a single host by item key, web scenario name and trigger description
+#[derive(Default)]
+struct ExistingObjects {
+    items: HashMap<String, ZabbixItem>,
+    web_scenarios: HashMap<String, ZabbixWebScenario>,
+    /// Web scenario names by url hash tag
+    web_scenario_names: HashMap<String, String>,
+    triggers: HashMap<String, ZabbixTrigger>,
+}
 
-            let request = GetItemsRequestByKey::new(&item_key);
+impl ExistingObjects {
+    /// Web scenario is found by url hash tag when its name was changed, i.e. with `name` option
+    fn get_web_scenario(&self, name: &str, url_hash: &str) -> Option<&ZabbixWebScenario> {
+        self.web_scenarios.get(name).or_else(|| {
+            self.web_scenario_names.get(url_hash)
+                .and_then(|name| self.web_scenarios.get(name))
+        })
+    }
+}
+
+/// Failed urls are recorded in `output`. Without `keep_going` option host processing stops
+/// at the first failed url, changes of the previous urls are applied.
+fn process_host(
+    context: &GenerationContext<impl ZabbixApiClient>,
+    host_url_sources: &HostUrlSources,
+    output: &mut GenerationOutput,
+) {
+    let zabbix_host = &host_url_sources.host;
+    let options = context.options;
+
+    let host_id = match &host_url_sources.status {
+        HostStatus::Resolved { host_id } => host_id,
+        HostStatus::Unresolved => {
+            for url_source in &host_url_sources.url_sources {
+                output.log_url(
+                    Level::Warn,
+                    Some(&url_source.url),
+                    format!("zabbix host '{zabbix_host}' wasn't found, skip url '{}'", url_source.url),
+                );
+                output.unresolved.push(UnresolvedUrl {
+                    host: zabbix_host.to_string(),
+                    url: url_source.url.to_string(),
+                });
+            }
+            return;
+        }
+    };
 
-            let items_found = zabbix_client.get_items(&session, &request)?;
+    output.log(Level::Info, format!("process host '{zabbix_host}', id {host_id}, urls: {}", host_url_sources.url_sources.len()));
 
-            if items_found.is_empty() {
-                let request = CreateItemRequest {
-                    name: process_template_string(&item_config.name_template, &template_vars),
-                    key_: item_key,
-                    host_id: host_id.to_string(),
-                    r#type: item_config.r#type,
-                    value_type: item_config.value_type,
-                    interface_id: item_config.interface_id.to_string(),
-                    tags: item_config.tags.clone(),
-                    delay: item_config.delay.to_string(),
-                };
+    let mut url_objects: Vec<UrlObjects> = vec![];
 
-                debug!("create item request: {:?}", request);
+    for url_source in &host_url_sources.url_sources {
+        match get_url_objects(context, zabbix_host, host_id, url_source) {
+            Ok(objects) => url_objects.push(objects),
+            Err(e) => {
+                output.fail(zabbix_host, &url_source.url, &e.context(format!("unable to process url '{}'", url_source.url)));
 
-                zabbix_client.create_item(&session, &request)?;
-            } else {
-                info!("item with key '{item_key}' already exists, skip")
+                // Urls prepared before the failed one are still applied
+                if !options.keep_going {
+                    break;
+                }
             }
+        }
+    }
 
-            let scenario_name =
-                process_template_string(&web_scenario_config.name_template, &template_vars);
+    let existing = match find_existing_objects(context, host_id, &url_objects) {
+        Ok(existing) => existing,
+        Err(e) => {
+            let e = e.context(format!("unable to get existing objects of host '{zabbix_host}'"));
 
-            let request = GetWebScenarioByNameRequest::new(&scenario_name);
+            for objects in &url_objects {
+                output.fail(zabbix_host, &objects.url_source.url, &e);
+            }
+            return;
+        }
+    };
 
-            let web_scenarios = zabbix_client.get_webscenarios(&session, &request)?;
+    let mut changes = HostChanges::default();
 
-            if web_scenarios.is_empty() {
-                let step = ZabbixWebScenarioStep {
-                    name: process_template_string(
-                        &web_scenario_config.name_template,
-                        &template_vars,
-                    ),
-                    url: url_source.url.to_string(),
-                    status_codes: web_scenario_config.expect_status_code.to_string(),
-                    no: "1".to_string(),
-                };
+    for objects in &url_objects {
+        let url = &objects.url_source.url;
 
-                let request = CreateWebScenarioRequest {
-                    name: scenario_name.to_string(),
-                    host_id: host_id.to_string(),
-                    steps: vec![step],
-                };
+        output.set_url(Some(url));
 
-                zabbix_client
-                    .create_webscenario(&session, &request)
-                    .context("unable to create web-scenario")?;
+        if let Err(e) = plan_url_objects(context, zabbix_host, objects, &existing, &mut changes, output) {
+            output.fail(zabbix_host, url, &e.context(format!("unable to process url '{url}'")));
 
-                info!("web scenario '{scenario_name}' has been created")
-            } else {
-                info!("web-scenario '{scenario_name}' already exists, skip");
+            // Planned changes are counted as failed
+            if !options.keep_going {
+                break;
             }
+        }
+    }
 
-            let trigger_description = process_template_string(&trigger_config.name, &template_vars);
+    output.set_url(None);
 
-            let request = GetTriggerByDescriptionRequest::new(&trigger_description);
+    if !options.dry_run {
+        let mut applied = AppliedChanges {
+            failures: mem::take(&mut output.failures),
+            ..Default::default()
+        };
+        let known_failures = applied.failures.len();
 
-            let triggers = zabbix_client.get_triggers(&session, &request)?;
+        changes.apply(context.zabbix_client, context.session, zabbix_host, options.keep_going, &mut applied);
 
-            if triggers.is_empty() {
-                info!("trigger '{trigger_description}' wasn't found, creating..");
+        for message in applied.messages {
+            output.log(Level::Info, message);
+        }
 
-                let mut recovery_mode: Option<u8> = None;
-                let mut recovery_expression: Option<String> = None;
+        for failure in &applied.failures[known_failures..] {
+            output.log_url(Level::Error, Some(&failure.url), format!("url '{}' failed: {}", failure.url, failure.error));
+        }
 
-                if !trigger_config.recovery_expression.is_empty() {
-                    recovery_mode = Some(trigger_config.recovery_mode);
-                    recovery_expression = Some(process_template_string(
-                        &trigger_config.recovery_expression,
-                        &template_vars,
-                    ));
-                }
+        output.failures = applied.failures;
+        output.failed_objects = applied.failed_objects;
+    }
+}
 
-                let mut url: Option<String> = None;
+/// Add actions for url objects to `output` plan and `changes`
+fn plan_url_objects(
+    context: &GenerationContext<impl ZabbixApiClient>,
+    zabbix_host: &str,
+    objects: &UrlObjects,
+    existing: &ExistingObjects,
+    changes: &mut HostChanges,
+    output: &mut GenerationOutput,
+) -> EmptyResult {
+    let options = context.options;
+    let url = &objects.url_source.url;
 
-                if !trigger_config.url.is_empty() {
-                    url = Some(process_template_string(&trigger_config.url, &template_vars));
-                }
+    output.log(Level::Info, format!("process url '{url}'"));
+    output.log(Level::Debug, format!("url source: {:?}", objects.url_source));
 
-                let mut event_name: Option<String> = None;
+    for request in &objects.items {
+        let item_key = &request.key_;
 
-                if !trigger_config.event_name.is_empty() {
-                    event_name = Some(process_template_string(
-                        &trigger_config.event_name,
-                        &template_vars,
-                    ));
-                }
+        let action = if changes.items.contains(item_key) {
+            ObjectAction::Skip
+        } else {
+            let existing_item = existing.items.get(item_key)
+                .map(|item| (item.item_id.to_string(), item));
+            get_object_action(request, existing_item, options.sync)?
+        };
 
-                let request = CreateTriggerRequest {
-                    description: trigger_description.to_string(),
-                    expression: process_template_string(
-                        &trigger_config.problem_expression,
-                        &template_vars,
-                    ),
-                    priority: trigger_config.priority,
-                    recovery_mode,
-                    recovery_expression,
-                    url,
-                    event_name,
-                    dependencies: vec![],
-                    tags: vec![],
-                };
-
-                debug!("create trigger request: {:?}", request);
-
-                zabbix_client.create_trigger(&session, &request)?;
-
-                info!("trigger '{trigger_description}' has been created")
-            } else {
-                info!("trigger '{trigger_description}' already exists, skip")
-            }
+        output.plan.add(ObjectType::Item, zabbix_host, url, item_key, request, &action)?;
+
+        if !options.dry_run && matches!(action, ObjectAction::Skip) {
+            output.log(Level::Info, format!("item with key '{item_key}' already exists, skip"));
+        }
+
+        changes.items.add(item_key, url, request, &action);
+    }
+
+    let request = &objects.web_scenario;
+    let scenario_name = &request.name;
+
+    let action = if changes.web_scenarios.contains(scenario_name) {
+        ObjectAction::Skip
+    } else {
+        let existing_web_scenario = existing.get_web_scenario(scenario_name, &get_url_hash(url))
+            .map(|web_scenario| (web_scenario.httptest_id.to_string(), web_scenario));
+        get_object_action(request, existing_web_scenario, options.sync)?
+    };
+
+    output.plan.add(ObjectType::WebScenario, zabbix_host, url, scenario_name, request, &action)?;
+
+    if !options.dry_run && matches!(action, ObjectAction::Skip) {
+        output.log(Level::Info, format!("web-scenario '{scenario_name}' already exists, skip"));
+    }
+
+    changes.web_scenarios.add(scenario_name, url, request, &action);
+
+    for request in &objects.triggers {
+        let trigger_description = &request.description;
+
+        let action = if changes.triggers.contains(trigger_description) {
+            ObjectAction::Skip
         } else {
-            warn!("zabbix host '{}' wasn't found, skip", zabbix_host)
+            let existing_trigger = existing.triggers.get(trigger_description)
+                .map(|trigger| (trigger.trigger_id.to_string(), trigger));
+            get_object_action(request, existing_trigger, options.sync)?
+        };
+
+        output.plan.add(ObjectType::Trigger, zabbix_host, url, trigger_description, request, &action)?;
+
+        if !options.dry_run && matches!(action, ObjectAction::Skip) {
+            output.log(Level::Info, format!("trigger '{trigger_description}' already exists, skip"));
         }
+
+        changes.triggers.add(trigger_description, url, request, &action);
     }
 
     Ok(())
 }
+
+fn get_url_objects<'a>(
+    context: &GenerationContext<impl ZabbixApiClient>,
+    zabbix_host: &str,
+    host_id: &str,
+    url_source: &'a UrlSource,
+) -> OperationResult<UrlObjects<'a>> {
+    let zabbix_client = context.zabbix_client;
+    let session = context.session;
+    let zabbix_config = context.zabbix_config;
+
+    let mut template_vars = get_template_vars(zabbix_host, &url_source.url);
+    let managed_tags = get_managed_tags(context.source_type, &url_source.url);
+
+    let mut web_scenario =
+        get_web_scenario_request(&zabbix_config.scenario, host_id, url_source, &template_vars)
+            .context("invalid web-scenario")?;
+    web_scenario.tags.extend(managed_tags.iter().cloned());
+
+    template_vars.insert(SCENARIO_NAME_TEMPLATE_VAR.to_string(), web_scenario.name.to_string());
+
+    let mut items: Vec<CreateItemRequest> = vec![];
+
+    for item_config in zabbix_config.get_items() {
+        let interface_id = context.interface_resolver.resolve(
+            zabbix_client,
+            session,
+            zabbix_host,
+            host_id,
+            item_config,
+        )?;
+
+        let mut request = get_item_request(item_config, host_id, &interface_id, &template_vars);
+        request.tags.extend(managed_tags.iter().cloned());
+        items.push(request);
+    }
+
+    let mut triggers: Vec<CreateTriggerRequest> = vec![];
+
+    for trigger_config in zabbix_config.get_triggers() {
+        let dependencies = context.dependency_resolver.resolve(
+            zabbix_client,
+            session,
+            &context.host_cache,
+            &trigger_config.dependencies,
+            &template_vars,
+        )?;
+
+        let mut request =
+            get_trigger_request(trigger_config, url_source, dependencies, &template_vars);
+        request.tags.extend(managed_tags.iter().cloned());
+        triggers.push(request);
+    }
+
+    Ok(UrlObjects {
+        url_source,
+        items,
+        web_scenario,
+        triggers,
+    })
+}
+
+/// One request per object type
+fn find_existing_objects(
+    context: &GenerationContext<impl ZabbixApiClient>,
+    host_id: &str,
+    url_objects: &[UrlObjects],
+) -> OperationResult<ExistingObjects> {
+    let zabbix_client = context.zabbix_client;
+    let session = context.session;
+
+    let mut results = ExistingObjects::default();
+
+    let item_keys: Vec<String> = url_objects.iter()
+        .flat_map(|objects| objects.items.iter().map(|item| item.key_.to_string()))
+        .collect();
+
+    if !item_keys.is_empty() {
+        for item in find_items(zabbix_client, session, host_id, &item_keys)? {
+            results.items.insert(item.key_.to_string(), item);
+        }
+    }
+
+    let scenario_names: Vec<String> = url_objects.iter()
+        .map(|objects| objects.web_scenario.name.to_string())
+        .collect();
+
+    if !scenario_names.is_empty() {
+        for web_scenario in find_web_scenarios(zabbix_client, session, host_id, &scenario_names)? {
+            results.web_scenarios.insert(web_scenario.name.to_string(), web_scenario);
+        }
+
+        // Renamed web scenarios
+        let url_hashes: Vec<String> = url_objects.iter()
+            .map(|objects| get_url_hash(&objects.url_source.url))
+            .collect();
+
+        for web_scenario in find_web_scenarios_by_tag_values(zabbix_client, session, host_id, URL_HASH_TAG, &url_hashes)? {
+            if let Some(url_hash) = web_scenario.get_tag_value(URL_HASH_TAG) {
+                results.web_scenario_names.insert(url_hash.to_string(), web_scenario.name.to_string());
+            }
+
+            results.web_scenarios.entry(web_scenario.name.to_string()).or_insert(web_scenario);
+        }
+    }
+
+    let trigger_descriptions: Vec<String> = url_objects.iter()
+        .flat_map(|objects| objects.triggers.iter().map(|trigger| trigger.description.to_string()))
+        .collect();
+
+    if !trigger_descriptions.is_empty() {
+        for trigger in find_triggers(zabbix_client, session, host_id, &trigger_descriptions)? {
+            results.triggers.insert(trigger.description.to_string(), trigger);
+        }
+    }
+
+    Ok(results)
+}
+
+/// Object is created when it doesn't exist. Existing object is updated in `sync` mode
+/// when it differs from the desired one.
+fn get_object_action(
+    desired: &impl Serialize,
+    existing: Option<(String, impl Serialize)>,
+    sync: bool,
+) -> OperationResult<ObjectAction> {
+    match existing {
+        None => Ok(ObjectAction::Create),
+        Some((id, existing)) if sync => {
+            let changes = get_changed_fields(desired, &existing)?;
+
+            if changes.is_empty() {
+                Ok(ObjectAction::Skip)
+            } else {
+                Ok(ObjectAction::Update { id, changes })
+            }
+        }
+        Some(_) => Ok(ObjectAction::Skip),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use serde_json::Map;
+    use zabbix_api::host::model::ZabbixHostTag;
+
+    use crate::command::generate::items::{group_by_host, resolve_hosts, ExistingObjects, HostStatus};
+    use crate::command::generate::managed::{get_url_hash, URL_HASH_TAG};
+    use crate::source::UrlSource;
+    use crate::zabbix::webscenario::ZabbixWebScenario;
+
+    fn get_url_source(host: &str, url: &str) -> UrlSource {
+        UrlSource {
+            zabbix_host: host.to_string(),
+            url: url.to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn url_sources_should_be_grouped_by_host_in_source_order() {
+        let url_sources = vec![
+            get_url_source("srv2", "https://a.com"),
+            get_url_source("srv1", "https://b.com"),
+            get_url_source("srv2", "https://c.com"),
+        ];
+
+        let groups = group_by_host(&url_sources, "");
+
+        assert_eq!(groups.len(), 2);
+        assert_eq!(groups[0].host, "srv2");
+        assert_eq!(groups[0].url_sources.iter().map(|us| us.url.as_str()).collect::<Vec<&str>>(),
+                   vec!["https://a.com", "https://c.com"]);
+        assert_eq!(groups[1].host, "srv1");
+
+        let groups = group_by_host(&url_sources, "web-checks");
+
+        assert_eq!(groups.len(), 1);
+        assert_eq!(groups[0].host, "web-checks");
+        assert_eq!(groups[0].url_sources.len(), 3);
+    }
+
+    #[test]
+    fn each_host_should_get_explicit_status() {
+        let url_sources = vec![
+            get_url_source("srv1", "https://a.com"),
+            get_url_source("srv2", "https://b.com"),
+            get_url_source("srv3", "https://c.com"),
+        ];
+
+        let mut groups = group_by_host(&url_sources, "");
+
+        let host_ids = HashMap::from([
+            ("srv1".to_string(), "10001".to_string()),
+            ("srv3".to_string(), "10003".to_string()),
+        ]);
+
+        resolve_hosts(&mut groups, &host_ids);
+
+        assert_eq!(groups[0].status, HostStatus::Resolved { host_id: "10001".to_string() });
+        assert_eq!(groups[1].status, HostStatus::Unresolved);
+        assert_eq!(groups[2].status, HostStatus::Resolved { host_id: "10003".to_string() });
+    }
+
+    #[test]
+    fn renamed_web_scenario_should_be_found_by_url_hash() {
+        let url_hash = get_url_hash("https://a.com");
+
+        let web_scenario = ZabbixWebScenario {
+            httptest_id: "100".to_string(),
+            name: "Check index page 'a.com'".to_string(),
+            host_id: "10001".to_string(),
+            steps: vec![],
+            tags: vec![ZabbixHostTag { tag: URL_HASH_TAG.to_string(), value: url_hash.to_string() }],
+            hosts: vec![],
+            fields: Map::new(),
+        };
+
+        let mut existing = ExistingObjects::default();
+        existing.web_scenario_names.insert(url_hash.to_string(), web_scenario.name.to_string());
+        existing.web_scenarios.insert(web_scenario.name.to_string(), web_scenario);
+
+        assert_eq!(existing.get_web_scenario("Check index page 'a.com'", "").unwrap().httptest_id, "100");
+        assert_eq!(existing.get_web_scenario("A health", &url_hash).unwrap().httptest_id, "100");
+        assert!(existing.get_web_scenario("B health", &get_url_hash("https://b.com")).is_none());
+    }
+}
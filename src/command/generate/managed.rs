@@ -0,0 +1,64 @@
+use zabbix_api::host::model::ZabbixHostTag;
+
+/// Marks objects created by wszl
+pub const MANAGED_BY_TAG: &str = "managed-by";
+pub const MANAGED_BY_TAG_VALUE: &str = "wszl";
+
+/// Url source type: zabbix, file, yaml, json
+pub const SOURCE_TAG: &str = "wszl-source";
+
+/// Links web scenario, items and triggers created for the same url
+pub const URL_HASH_TAG: &str = "wszl-url-hash";
+
+const FNV_OFFSET_BASIS: u64 = 0xcbf29ce484222325;
+const FNV_PRIME: u64 = 0x100000001b3;
+
+/// Tags added to every web scenario, item and trigger created by wszl
+pub fn get_managed_tags(source_type: &str, url: &str) -> Vec<ZabbixHostTag> {
+    vec![
+        ZabbixHostTag {
+            tag: MANAGED_BY_TAG.to_string(),
+            value: MANAGED_BY_TAG_VALUE.to_string(),
+        },
+        ZabbixHostTag {
+            tag: SOURCE_TAG.to_string(),
+            value: source_type.to_string(),
+        },
+        ZabbixHostTag {
+            tag: URL_HASH_TAG.to_string(),
+            value: get_url_hash(url),
+        },
+    ]
+}
+
+/// FNV-1a hash, stable between runs and Rust versions unlike `DefaultHasher`.
+/// Urls may be longer than max tag value length, so hash is used instead of url.
+pub fn get_url_hash(url: &str) -> String {
+    let hash = url.bytes().fold(FNV_OFFSET_BASIS, |hash, byte| {
+        (hash ^ byte as u64).wrapping_mul(FNV_PRIME)
+    });
+
+    format!("{hash:016x}")
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::command::generate::managed::{get_managed_tags, get_url_hash, URL_HASH_TAG};
+
+    #[test]
+    fn url_hash_should_be_stable() {
+        assert_eq!(get_url_hash(""), "cbf29ce484222325");
+        assert_eq!(get_url_hash("a"), "af63dc4c8601ec8c");
+        assert_eq!(get_url_hash("https://zabbix.com"), get_url_hash("https://zabbix.com"));
+        assert_ne!(get_url_hash("https://zabbix.com"), get_url_hash("https://zabbix.com/"));
+        assert_eq!(get_url_hash("https://zabbix.com").len(), 16);
+    }
+
+    #[test]
+    fn managed_tags_should_contain_url_hash() {
+        let tags = get_managed_tags("file", "https://zabbix.com");
+
+        assert_eq!(tags.len(), 3);
+        assert!(tags.iter().any(|t| t.tag == URL_HASH_TAG && t.value == get_url_hash("https://zabbix.com")));
+    }
+}
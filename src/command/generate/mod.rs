@@ -1 +1,31 @@
-pub mod items;
\ No newline at end of file
+pub mod batch;
+pub mod dependencies;
+pub mod interfaces;
+pub mod items;
+pub mod managed;
+pub mod plan;
+pub mod prune;
+pub mod report;
+pub mod requests;
+pub mod worker;
+
+#[derive(Default, Clone, Debug)]
+pub struct GenerateOptions {
+    /// Update existing objects which differ from configuration
+    pub sync: bool,
+
+    /// Disable or delete objects which urls are absent in url source
+    pub prune: bool,
+
+    /// Only collect plan of actions, don't change anything in Zabbix
+    pub dry_run: bool,
+
+    /// Urls with unresolved hosts fail the run
+    pub strict: bool,
+
+    /// Process remaining urls when some of them fail
+    pub keep_going: bool,
+
+    /// Number of hosts processed in parallel
+    pub concurrency: usize,
+}
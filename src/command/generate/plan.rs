@@ -0,0 +1,252 @@
+use std::fmt::{Display, Formatter};
+
+use serde::Serialize;
+use serde_json::{Map, Value};
+
+use crate::types::{EmptyResult, OperationResult};
+
+/// Objects which are created, updated or skipped during generation
+#[derive(Serialize, Default, Debug)]
+#[serde(rename_all = "kebab-case")]
+pub struct Plan {
+    pub actions: Vec<PlannedAction>,
+}
+
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "kebab-case")]
+pub struct PlannedAction {
+    pub action: ActionType,
+    pub object_type: ObjectType,
+    pub host: String,
+    pub url: String,
+    pub name: String,
+
+    /// Rendered object
+    pub object: Value,
+
+    /// Fields to update
+    #[serde(skip_serializing_if = "Map::is_empty")]
+    pub changes: Map<String, Value>,
+}
+
+#[derive(Serialize, PartialEq, Clone, Copy, Debug)]
+#[serde(rename_all = "kebab-case")]
+pub enum ActionType {
+    Create,
+    Update,
+    Skip,
+    Disable,
+    Restore,
+    Delete,
+}
+
+#[derive(Serialize, PartialEq, Clone, Copy, Debug)]
+#[serde(rename_all = "kebab-case")]
+pub enum ObjectType {
+    Item,
+    WebScenario,
+    Trigger,
+}
+
+/// What to do with the object
+#[derive(Debug)]
+pub enum ObjectAction {
+    Create,
+    Update { id: String, changes: Map<String, Value> },
+    Skip,
+}
+
+impl ObjectAction {
+    pub fn get_type(&self) -> ActionType {
+        match self {
+            ObjectAction::Create => ActionType::Create,
+            ObjectAction::Update { .. } => ActionType::Update,
+            ObjectAction::Skip => ActionType::Skip,
+        }
+    }
+}
+
+impl Plan {
+    pub fn add(
+        &mut self,
+        object_type: ObjectType,
+        host: &str,
+        url: &str,
+        name: &str,
+        object: &impl Serialize,
+        action: &ObjectAction,
+    ) -> EmptyResult {
+        let changes = match action {
+            ObjectAction::Update { changes, .. } => changes.clone(),
+            _ => Map::new(),
+        };
+
+        self.actions.push(PlannedAction {
+            action: action.get_type(),
+            object_type,
+            host: host.to_string(),
+            url: url.to_string(),
+            name: name.to_string(),
+            object: serde_json::to_value(object)?,
+            changes,
+        });
+
+        Ok(())
+    }
+
+    /// Add action for existing object, i.e. disable orphaned web scenario
+    pub fn add_existing(
+        &mut self,
+        action: ActionType,
+        object_type: ObjectType,
+        host: &str,
+        url: &str,
+        name: &str,
+    ) {
+        self.actions.push(PlannedAction {
+            action,
+            object_type,
+            host: host.to_string(),
+            url: url.to_string(),
+            name: name.to_string(),
+            object: Value::Null,
+            changes: Map::new(),
+        });
+    }
+
+    pub fn get_count(&self, action: ActionType) -> usize {
+        self.actions.iter().filter(|a| a.action == action).count()
+    }
+
+    pub fn to_json(&self) -> OperationResult<String> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+}
+
+impl Display for Plan {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for action in &self.actions {
+            writeln!(f, "{action}")?;
+        }
+
+        write!(
+            f,
+            "Plan: {} to create, {} to update, {} to skip, {} to disable, {} to restore, {} to delete",
+            self.get_count(ActionType::Create),
+            self.get_count(ActionType::Update),
+            self.get_count(ActionType::Skip),
+            self.get_count(ActionType::Disable),
+            self.get_count(ActionType::Restore),
+            self.get_count(ActionType::Delete),
+        )
+    }
+}
+
+impl Display for PlannedAction {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let sign = match self.action {
+            ActionType::Create => "+",
+            ActionType::Update | ActionType::Restore => "~",
+            ActionType::Skip => " ",
+            ActionType::Disable | ActionType::Delete => "-",
+        };
+
+        writeln!(
+            f,
+            "{sign} {} {} '{}' on host '{}' (url '{}')",
+            self.action, self.object_type, self.name, self.host, self.url
+        )?;
+
+        if self.action == ActionType::Create {
+            write_fields(f, self.object.as_object())
+        } else {
+            write_fields(f, Some(&self.changes))
+        }
+    }
+}
+
+fn write_fields(f: &mut Formatter<'_>, fields: Option<&Map<String, Value>>) -> std::fmt::Result {
+    if let Some(fields) = fields {
+        for (key, value) in fields {
+            writeln!(f, "      {key}: {value}")?;
+        }
+    }
+
+    Ok(())
+}
+
+impl Display for ActionType {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let value = match self {
+            ActionType::Create => "create",
+            ActionType::Update => "update",
+            ActionType::Skip => "skip",
+            ActionType::Disable => "disable",
+            ActionType::Restore => "restore",
+            ActionType::Delete => "delete",
+        };
+
+        write!(f, "{value}")
+    }
+}
+
+impl Display for ObjectType {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let value = match self {
+            ObjectType::Item => "item",
+            ObjectType::WebScenario => "web-scenario",
+            ObjectType::Trigger => "trigger",
+        };
+
+        write!(f, "{value}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::{json, Map};
+
+    use crate::command::generate::plan::{ObjectAction, ObjectType, Plan};
+
+    #[test]
+    fn plan_should_be_rendered_as_text_and_json() {
+        let mut plan = Plan::default();
+
+        plan.add(
+            ObjectType::WebScenario,
+            "srv1",
+            "https://zabbix.com",
+            "Check index page 'zabbix.com'",
+            &json!({"name": "Check index page 'zabbix.com'", "delay": "5m"}),
+            &ObjectAction::Create,
+        )
+        .unwrap();
+
+        let mut changes = Map::new();
+        changes.insert("priority".to_string(), json!(4));
+
+        plan.add(
+            ObjectType::Trigger,
+            "srv1",
+            "https://zabbix.com",
+            "Site 'zabbix.com' is unavailable",
+            &json!({"priority": 4}),
+            &ObjectAction::Update { id: "15".to_string(), changes },
+        )
+        .unwrap();
+
+        let text = plan.to_string();
+
+        assert!(text.contains("+ create web-scenario 'Check index page 'zabbix.com'' on host 'srv1'"));
+        assert!(text.contains("delay: \"5m\""));
+        assert!(text.contains("~ update trigger 'Site 'zabbix.com' is unavailable'"));
+        assert!(text.contains("priority: 4"));
+        assert!(text.ends_with("Plan: 1 to create, 1 to update, 0 to skip, 0 to disable, 0 to restore, 0 to delete"));
+
+        let json: serde_json::Value = serde_json::from_str(&plan.to_json().unwrap()).unwrap();
+
+        assert_eq!(json["actions"][0]["action"], "create");
+        assert_eq!(json["actions"][0]["object-type"], "web-scenario");
+        assert_eq!(json["actions"][1]["changes"]["priority"], 4);
+    }
+}
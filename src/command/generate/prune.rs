@@ -0,0 +1,484 @@
+use std::collections::HashSet;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use anyhow::Context;
+use serde_json::{json, Map, Value};
+use zabbix_api::client::client::ZabbixApiClient;
+use zabbix_api::host::model::ZabbixHostTag;
+
+use crate::command::generate::managed::{get_url_hash, SOURCE_TAG, URL_HASH_TAG};
+use crate::command::generate::plan::{ActionType, ObjectType, Plan};
+use crate::command::generate::GenerateOptions;
+use crate::config::duration::parse_duration;
+use crate::config::prune::PruneAction;
+use crate::config::ZabbixConfig;
+use crate::source::{InvalidUrl, UrlSource, UrlSources};
+use crate::types::{EmptyResult, OperationResult};
+use crate::zabbix::api::delete_objects;
+use crate::template::{get_template_vars, process_template_string, SCENARIO_NAME_TEMPLATE_VAR};
+use crate::zabbix::item::{find_items, find_items_by_tag, update_item};
+use crate::zabbix::trigger::{find_triggers, find_triggers_by_tag, update_trigger};
+use crate::zabbix::webscenario::{
+    find_web_scenarios_by_name_prefix, find_web_scenarios_by_tag, update_web_scenario, ZabbixWebScenario,
+};
+
+/// Holds unix timestamp when web scenario was considered orphaned
+pub const ORPHANED_SINCE_TAG: &str = "wszl-orphaned-since";
+
+const STATUS_ENABLED: u8 = 0;
+const STATUS_DISABLED: u8 = 1;
+
+/// Disable or delete web scenarios, items and triggers created by wszl from the same
+/// url source type, which urls are absent on their hosts in url sources.
+///
+/// Objects are recognized by `URL_HASH_TAG` and `SOURCE_TAG` tags. Web scenarios created by older
+/// versions have no tags, they are recognized by `scenario.key-starts-with` name prefix and url of
+/// the first step regardless of url source type. Urls skipped by url source,
+/// i.e. rows with invalid options, are considered present. Nothing is pruned when url source
+/// returned no urls at all, i.e. empty file.
+///
+/// Orphaned objects are disabled first, `delete` action removes them on the following runs
+/// once grace period expires.
+pub fn prune_orphaned_objects(
+    zabbix_client: &impl ZabbixApiClient,
+    session: &str,
+    url_sources: &UrlSources,
+    source_type: &str,
+    zabbix_config: &ZabbixConfig,
+    options: &GenerateOptions,
+    plan: &mut Plan,
+) -> EmptyResult {
+    info!("prune orphaned web scenarios..");
+
+    let prune_config = &zabbix_config.prune;
+
+    let grace_period = parse_duration(&prune_config.grace_period)
+        .context("invalid prune grace period")?
+        .as_secs();
+
+    if url_sources.url_sources.is_empty() && url_sources.invalid_urls.is_empty() {
+        warn!("url source returned no urls, prune is skipped");
+        return Ok(());
+    }
+
+    let present_urls = get_present_urls(
+        &url_sources.url_sources,
+        &url_sources.invalid_urls,
+        &zabbix_config.target_hostname,
+    );
+
+    let mut candidates: Vec<PruneCandidate> = vec![];
+
+    for web_scenario in find_web_scenarios_by_tag(zabbix_client, session, URL_HASH_TAG)? {
+        // Objects of other url sources are pruned by their own runs
+        if web_scenario.get_tag_value(SOURCE_TAG) != Some(source_type) {
+            continue;
+        }
+
+        if let Some(url_hash) = web_scenario.get_tag_value(URL_HASH_TAG) {
+            let url_hash = url_hash.to_string();
+            candidates.push(PruneCandidate { web_scenario, url_hash, untagged: false });
+        }
+    }
+
+    let key_starts_with = &zabbix_config.scenario.key_starts_with;
+
+    if !key_starts_with.is_empty() {
+        let web_scenarios = find_web_scenarios_by_name_prefix(zabbix_client, session, key_starts_with)?;
+        let untagged = get_untagged_candidates(web_scenarios);
+
+        if !untagged.is_empty() {
+            info!("untagged web-scenarios with name prefix '{key_starts_with}': {}", untagged.len());
+        }
+
+        candidates.extend(untagged);
+    }
+
+    let now = get_unix_timestamp();
+
+    for candidate in candidates {
+        let web_scenario = candidate.web_scenario;
+        let url_hash = candidate.url_hash;
+
+        let hostname = get_hostname(&web_scenario);
+
+        // Url of the first step, used in plan and logs only
+        let url = web_scenario.get_url().unwrap_or_default().to_string();
+
+        let orphaned_since = web_scenario
+            .get_tag_value(ORPHANED_SINCE_TAG)
+            .and_then(|value| value.parse::<u64>().ok());
+
+        let present = present_urls.contains(&(hostname.to_string(), url_hash.to_string()));
+
+        let action = match get_prune_action(present, orphaned_since, prune_config.action, now, grace_period) {
+            Some(action) => action,
+            None => {
+                if let Some(since) = orphaned_since {
+                    debug!("web-scenario '{}' is orphaned since {since}, wait", web_scenario.name);
+                }
+                continue;
+            }
+        };
+
+        let related = match candidate.untagged {
+            true => RelatedObjects::find_untagged(zabbix_client, session, &web_scenario, &url, zabbix_config)?,
+            false => RelatedObjects::find(zabbix_client, session, &web_scenario, &url_hash)?,
+        };
+
+        plan.add_existing(action, ObjectType::WebScenario, &hostname, &url, &web_scenario.name);
+
+        for (_, item_key) in &related.items {
+            plan.add_existing(action, ObjectType::Item, &hostname, &url, item_key);
+        }
+
+        for (_, trigger_description) in &related.triggers {
+            plan.add_existing(action, ObjectType::Trigger, &hostname, &url, trigger_description);
+        }
+
+        if options.dry_run {
+            continue;
+        }
+
+        match action {
+            ActionType::Restore => {
+                info!("url '{url}' is back in source, restore web-scenario '{}'", web_scenario.name);
+                set_status(zabbix_client, session, &web_scenario, &related, STATUS_ENABLED, None)?;
+            }
+            ActionType::Delete => {
+                info!("delete orphaned web-scenario '{}' (url '{url}')", web_scenario.name);
+                delete_all(zabbix_client, session, &web_scenario, &related)?;
+            }
+            _ => {
+                info!("disable orphaned web-scenario '{}' (url '{url}')", web_scenario.name);
+                set_status(
+                    zabbix_client,
+                    session,
+                    &web_scenario,
+                    &related,
+                    STATUS_DISABLED,
+                    Some(now),
+                )?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Orphaned object is disabled on the first run, even with `delete` action. Returns `None`
+/// when nothing has to be done.
+fn get_prune_action(
+    present: bool,
+    orphaned_since: Option<u64>,
+    prune_action: PruneAction,
+    now: u64,
+    grace_period: u64,
+) -> Option<ActionType> {
+    match (present, orphaned_since) {
+        (true, Some(_)) => Some(ActionType::Restore),
+        (true, None) => None,
+        (false, None) => Some(ActionType::Disable),
+        (false, Some(since)) => {
+            if prune_action == PruneAction::Delete && now >= since + grace_period {
+                Some(ActionType::Delete)
+            } else {
+                None
+            }
+        }
+    }
+}
+
+/// Host and url hash of every url, objects are created on `target_hostname` when it's set
+fn get_present_urls(
+    url_sources: &[UrlSource],
+    invalid_urls: &[InvalidUrl],
+    target_hostname: &str,
+) -> HashSet<(String, String)> {
+    let urls = url_sources.iter()
+        .map(|url_source| (&url_source.zabbix_host, &url_source.url))
+        .chain(invalid_urls.iter().map(|invalid_url| (&invalid_url.host, &invalid_url.url)));
+
+    urls.map(|(host, url)| {
+            let host = if target_hostname.is_empty() { host } else { target_hostname };
+            (host.to_string(), get_url_hash(url))
+        })
+        .collect()
+}
+
+/// Web scenarios without url hash tag, url hash is calculated from url of the first step
+fn get_untagged_candidates(web_scenarios: Vec<ZabbixWebScenario>) -> Vec<PruneCandidate> {
+    web_scenarios.into_iter()
+        .filter(|web_scenario| web_scenario.get_tag_value(URL_HASH_TAG).is_none())
+        .filter_map(|web_scenario| {
+            let url_hash = get_url_hash(web_scenario.get_url()?);
+            Some(PruneCandidate { web_scenario, url_hash, untagged: true })
+        })
+        .collect()
+}
+
+fn get_hostname(web_scenario: &ZabbixWebScenario) -> String {
+    match web_scenario.hosts.first() {
+        Some(host) => host.host.to_string(),
+        None => String::new(),
+    }
+}
+
+/// Web scenario created by wszl which url may be absent in url source
+struct PruneCandidate {
+    web_scenario: ZabbixWebScenario,
+    url_hash: String,
+
+    /// Created by older versions, found by `scenario.key-starts-with` name prefix
+    untagged: bool,
+}
+
+/// Items and triggers created by wszl for the same url
+struct RelatedObjects {
+    /// Item id and key
+    items: Vec<(String, String)>,
+
+    /// Trigger id and description
+    triggers: Vec<(String, String)>,
+}
+
+impl RelatedObjects {
+    fn find(
+        zabbix_client: &impl ZabbixApiClient,
+        session: &str,
+        web_scenario: &ZabbixWebScenario,
+        url_hash: &str,
+    ) -> OperationResult<RelatedObjects> {
+        let host_id = &web_scenario.host_id;
+
+        let items = find_items_by_tag(zabbix_client, session, host_id, URL_HASH_TAG, url_hash)?
+            .into_iter()
+            .map(|item| (item.item_id, item.key_))
+            .collect();
+
+        let triggers =
+            find_triggers_by_tag(zabbix_client, session, host_id, URL_HASH_TAG, url_hash)?
+                .into_iter()
+                .map(|trigger| (trigger.trigger_id, trigger.description))
+                .collect();
+
+        Ok(RelatedObjects { items, triggers })
+    }
+
+    /// Objects created by older versions have no tags, they are found by item keys
+    /// and trigger names from config
+    fn find_untagged(
+        zabbix_client: &impl ZabbixApiClient,
+        session: &str,
+        web_scenario: &ZabbixWebScenario,
+        url: &str,
+        zabbix_config: &ZabbixConfig,
+    ) -> OperationResult<RelatedObjects> {
+        let host_id = &web_scenario.host_id;
+
+        let mut template_vars = get_template_vars(&get_hostname(web_scenario), url);
+        template_vars.insert(SCENARIO_NAME_TEMPLATE_VAR.to_string(), web_scenario.name.to_string());
+
+        let item_keys: Vec<String> = zabbix_config.get_items().iter()
+            .map(|item_config| process_template_string(&item_config.key_template, &template_vars))
+            .collect();
+
+        let items = match item_keys.is_empty() {
+            true => vec![],
+            false => find_items(zabbix_client, session, host_id, &item_keys)?
+                .into_iter()
+                .map(|item| (item.item_id, item.key_))
+                .collect(),
+        };
+
+        let trigger_descriptions: Vec<String> = zabbix_config.get_triggers().iter()
+            .map(|trigger_config| process_template_string(&trigger_config.name, &template_vars))
+            .collect();
+
+        let triggers = match trigger_descriptions.is_empty() {
+            true => vec![],
+            false => find_triggers(zabbix_client, session, host_id, &trigger_descriptions)?
+                .into_iter()
+                .map(|trigger| (trigger.trigger_id, trigger.description))
+                .collect(),
+        };
+
+        Ok(RelatedObjects { items, triggers })
+    }
+}
+
+/// Set status for web scenario and related objects. Web scenario is marked with
+/// `ORPHANED_SINCE_TAG` when `orphaned_since` is set, otherwise the mark is removed.
+fn set_status(
+    zabbix_client: &impl ZabbixApiClient,
+    session: &str,
+    web_scenario: &ZabbixWebScenario,
+    related: &RelatedObjects,
+    status: u8,
+    orphaned_since: Option<u64>,
+) -> EmptyResult {
+    let mut tags: Vec<ZabbixHostTag> = web_scenario
+        .tags
+        .iter()
+        .filter(|tag| tag.tag != ORPHANED_SINCE_TAG)
+        .cloned()
+        .collect();
+
+    if let Some(timestamp) = orphaned_since {
+        tags.push(ZabbixHostTag {
+            tag: ORPHANED_SINCE_TAG.to_string(),
+            value: timestamp.to_string(),
+        });
+    }
+
+    let mut fields = get_status_fields(status);
+    fields.insert("tags".to_string(), json!(tags));
+
+    update_web_scenario(zabbix_client, session, &web_scenario.httptest_id, &fields)
+        .context("unable to update web-scenario status")?;
+
+    for (trigger_id, _) in &related.triggers {
+        update_trigger(zabbix_client, session, trigger_id, &get_status_fields(status))
+            .context("unable to update trigger status")?;
+    }
+
+    for (item_id, _) in &related.items {
+        update_item(zabbix_client, session, item_id, &get_status_fields(status))
+            .context("unable to update item status")?;
+    }
+
+    Ok(())
+}
+
+fn delete_all(
+    zabbix_client: &impl ZabbixApiClient,
+    session: &str,
+    web_scenario: &ZabbixWebScenario,
+    related: &RelatedObjects,
+) -> EmptyResult {
+    let trigger_ids: Vec<String> = related.triggers.iter().map(|(id, _)| id.to_string()).collect();
+
+    if !trigger_ids.is_empty() {
+        delete_objects(zabbix_client, session, "trigger.delete", &trigger_ids)
+            .context("unable to delete triggers")?;
+    }
+
+    delete_objects(
+        zabbix_client,
+        session,
+        "httptest.delete",
+        &[web_scenario.httptest_id.to_string()],
+    )
+    .context("unable to delete web-scenario")?;
+
+    let item_ids: Vec<String> = related.items.iter().map(|(id, _)| id.to_string()).collect();
+
+    if !item_ids.is_empty() {
+        delete_objects(zabbix_client, session, "item.delete", &item_ids)
+            .context("unable to delete items")?;
+    }
+
+    Ok(())
+}
+
+fn get_status_fields(status: u8) -> Map<String, Value> {
+    let mut fields = Map::new();
+    fields.insert("status".to_string(), json!(status));
+    fields
+}
+
+fn get_unix_timestamp() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::Map;
+    use zabbix_api::host::model::ZabbixHostTag;
+
+    use crate::command::generate::managed::{get_url_hash, URL_HASH_TAG};
+    use crate::command::generate::plan::ActionType;
+    use crate::command::generate::prune::{get_present_urls, get_prune_action, get_untagged_candidates};
+    use crate::config::prune::PruneAction;
+    use crate::source::{InvalidUrl, UrlSource};
+    use crate::zabbix::webscenario::{ZabbixWebScenario, ZabbixWebScenarioStep};
+
+    fn get_web_scenario(name: &str, url: Option<&str>, tags: Vec<ZabbixHostTag>) -> ZabbixWebScenario {
+        ZabbixWebScenario {
+            httptest_id: "100".to_string(),
+            name: name.to_string(),
+            host_id: "10001".to_string(),
+            steps: url.into_iter()
+                .map(|url| ZabbixWebScenarioStep { no: "1".to_string(), url: url.to_string(), fields: Map::new() })
+                .collect(),
+            tags,
+            hosts: vec![],
+            fields: Map::new(),
+        }
+    }
+
+    fn get_url_source(host: &str, url: &str) -> UrlSource {
+        UrlSource {
+            zabbix_host: host.to_string(),
+            host_id: None,
+            url: url.to_string(),
+            overrides: Default::default(),
+        }
+    }
+
+    #[test]
+    fn present_urls_should_be_keyed_by_host() {
+        let url_sources = vec![get_url_source("srv2", "https://a.com")];
+
+        let present_urls = get_present_urls(&url_sources, &[], "");
+
+        assert!(present_urls.contains(&("srv2".to_string(), get_url_hash("https://a.com"))));
+        assert!(!present_urls.contains(&("srv1".to_string(), get_url_hash("https://a.com"))));
+
+        let present_urls = get_present_urls(&url_sources, &[], "target");
+
+        assert!(present_urls.contains(&("target".to_string(), get_url_hash("https://a.com"))));
+    }
+
+    #[test]
+    fn invalid_urls_should_be_considered_present() {
+        let invalid_urls = vec![InvalidUrl {
+            host: "srv1".to_string(),
+            url: "https://b.com".to_string(),
+            reason: "line 2: invalid priority value 'high'".to_string(),
+        }];
+
+        let present_urls = get_present_urls(&[], &invalid_urls, "");
+
+        assert!(present_urls.contains(&("srv1".to_string(), get_url_hash("https://b.com"))));
+    }
+
+    #[test]
+    fn orphaned_objects_should_be_disabled_before_delete() {
+        assert_eq!(get_prune_action(false, None, PruneAction::Delete, 100, 0), Some(ActionType::Disable));
+        assert_eq!(get_prune_action(false, Some(100), PruneAction::Delete, 100, 0), Some(ActionType::Delete));
+        assert_eq!(get_prune_action(false, Some(100), PruneAction::Delete, 150, 60), None);
+        assert_eq!(get_prune_action(false, Some(100), PruneAction::Disable, 1000, 0), None);
+        assert_eq!(get_prune_action(true, Some(100), PruneAction::Delete, 1000, 0), Some(ActionType::Restore));
+        assert_eq!(get_prune_action(true, None, PruneAction::Delete, 1000, 0), None);
+    }
+
+    #[test]
+    fn untagged_web_scenarios_should_be_keyed_by_step_url() {
+        let tags = vec![ZabbixHostTag { tag: URL_HASH_TAG.to_string(), value: get_url_hash("https://b.com") }];
+
+        let candidates = get_untagged_candidates(vec![
+            get_web_scenario("Check index page 'a.com'", Some("https://a.com"), vec![]),
+            get_web_scenario("Check index page 'b.com'", Some("https://b.com"), tags),
+            get_web_scenario("Check index page 'c.com'", None, vec![]),
+        ]);
+
+        assert_eq!(candidates.len(), 1);
+        assert_eq!(candidates[0].web_scenario.name, "Check index page 'a.com'");
+        assert_eq!(candidates[0].url_hash, get_url_hash("https://a.com"));
+        assert!(candidates[0].untagged);
+    }
+}
@@ -0,0 +1,260 @@
+use std::fmt::{Display, Formatter};
+
+use anyhow::anyhow;
+use serde::Serialize;
+
+use crate::command::generate::plan::{ActionType, ObjectType, Plan};
+use crate::source::InvalidUrl;
+use crate::types::{AppError, OperationResult};
+
+/// Outcome of generation. Printed as a table at the end of run, written as JSON with `--report-file`
+#[derive(Serialize, Default, Debug)]
+#[serde(rename_all = "kebab-case")]
+pub struct Report {
+    /// Performed actions, printed for dry run
+    #[serde(skip)]
+    pub plan: Plan,
+
+    pub items: ObjectCounts,
+    pub web_scenarios: ObjectCounts,
+    pub triggers: ObjectCounts,
+
+    /// Urls which hosts weren't found in Zabbix, nothing is generated for them
+    pub unresolved: Vec<UnresolvedUrl>,
+
+    /// Urls skipped by url source, i.e. invalid options
+    pub invalid: Vec<InvalidUrl>,
+
+    /// Urls which objects weren't generated because of errors
+    pub failures: Vec<UrlFailure>,
+}
+
+/// With dry run objects are counted as planned
+#[derive(Serialize, PartialEq, Default, Clone, Copy, Debug)]
+#[serde(rename_all = "kebab-case")]
+pub struct ObjectCounts {
+    pub created: usize,
+    pub updated: usize,
+    pub skipped: usize,
+    pub failed: usize,
+}
+
+#[derive(Serialize, PartialEq, Clone, Debug)]
+#[serde(rename_all = "kebab-case")]
+pub struct UnresolvedUrl {
+    pub host: String,
+    pub url: String,
+}
+
+#[derive(Serialize, PartialEq, Clone, Debug)]
+#[serde(rename_all = "kebab-case")]
+pub struct UrlFailure {
+    pub host: String,
+    pub url: String,
+    pub error: String,
+    pub root_cause: String,
+
+    /// Zabbix API returned error, i.e. invalid trigger expression
+    pub api_error: bool,
+}
+
+impl UrlFailure {
+    pub fn new(host: &str, url: &str, error: &anyhow::Error) -> UrlFailure {
+        UrlFailure {
+            host: host.to_string(),
+            url: url.to_string(),
+            error: error.to_string(),
+            root_cause: error.root_cause().to_string(),
+            api_error: AppError::is_api_error(error),
+        }
+    }
+}
+
+impl Report {
+    /// Count actions and move them to report plan. Objects to create or update are counted
+    /// as failed when they are in `failed_objects` (object type, name).
+    pub fn add_plan(&mut self, plan: Plan, failed_objects: &[(ObjectType, String)]) {
+        for action in &plan.actions {
+            let failed = failed_objects.iter()
+                .any(|(object_type, name)| *object_type == action.object_type && *name == action.name);
+
+            let counts = match action.object_type {
+                ObjectType::Item => &mut self.items,
+                ObjectType::WebScenario => &mut self.web_scenarios,
+                ObjectType::Trigger => &mut self.triggers,
+            };
+
+            // Prune actions are listed in plan and log
+            match action.action {
+                ActionType::Create | ActionType::Update if failed => counts.failed += 1,
+                ActionType::Create => counts.created += 1,
+                ActionType::Update => counts.updated += 1,
+                ActionType::Skip => counts.skipped += 1,
+                ActionType::Disable | ActionType::Restore | ActionType::Delete => {}
+            }
+        }
+
+        self.plan.actions.extend(plan.actions);
+    }
+
+    pub fn has_failures(&self) -> bool {
+        !self.failures.is_empty()
+    }
+
+    /// Failed urls fail the run, urls with unresolved hosts fail it with `strict` as well.
+    /// Zabbix API errors take precedence over the rest of failures.
+    pub fn get_error(&self, strict: bool) -> Option<AppError> {
+        let api_errors = self.failures.iter().filter(|failure| failure.api_error).count();
+
+        if api_errors > 0 {
+            return Some(AppError::Api(anyhow!("zabbix api rejected objects of {api_errors} urls")));
+        }
+
+        let unresolved = if strict { self.unresolved.len() } else { 0 };
+
+        if self.has_failures() || unresolved > 0 {
+            return Some(AppError::PartialFailure { failed: self.failures.len(), unresolved });
+        }
+
+        None
+    }
+
+    pub fn to_json(&self) -> OperationResult<String> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+}
+
+impl Display for Report {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "{:<14}{:>9}{:>9}{:>9}{:>9}", "OBJECT", "CREATED", "UPDATED", "SKIPPED", "FAILED")?;
+
+        for (object_type, counts) in [
+            (ObjectType::Item, &self.items),
+            (ObjectType::WebScenario, &self.web_scenarios),
+            (ObjectType::Trigger, &self.triggers),
+        ] {
+            writeln!(
+                f,
+                "{:<14}{:>9}{:>9}{:>9}{:>9}",
+                object_type.to_string(), counts.created, counts.updated, counts.skipped, counts.failed
+            )?;
+        }
+
+        if !self.unresolved.is_empty() {
+            writeln!(f, "Unresolved hosts ({} urls):", self.unresolved.len())?;
+
+            for unresolved_url in &self.unresolved {
+                writeln!(f, "- host '{}', url '{}'", unresolved_url.host, unresolved_url.url)?;
+            }
+        }
+
+        if !self.invalid.is_empty() {
+            writeln!(f, "Invalid urls ({}):", self.invalid.len())?;
+
+            for invalid_url in &self.invalid {
+                writeln!(f, "- host '{}', url '{}': {}", invalid_url.host, invalid_url.url, invalid_url.reason)?;
+            }
+        }
+
+        if !self.failures.is_empty() {
+            writeln!(f, "Failed urls ({}):", self.failures.len())?;
+
+            for failure in &self.failures {
+                writeln!(f, "- host '{}', url '{}': {} ({})", failure.host, failure.url, failure.error, failure.root_cause)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::{json, Map};
+
+    use crate::command::generate::plan::{ObjectAction, ObjectType, Plan};
+    use anyhow::anyhow;
+
+    use crate::command::generate::report::{ObjectCounts, Report, UnresolvedUrl, UrlFailure};
+    use crate::types::AppError;
+
+    fn get_plan(actions: Vec<(ObjectType, ObjectAction)>) -> Plan {
+        let mut plan = Plan::default();
+
+        for (object_type, action) in actions {
+            plan.add(object_type, "srv1", "https://a.com", "name", &json!({}), &action).unwrap();
+        }
+
+        plan
+    }
+
+    #[test]
+    fn actions_should_be_counted_per_object_type() {
+        let mut report = Report::default();
+
+        report.add_plan(get_plan(vec![
+            (ObjectType::Item, ObjectAction::Create),
+            (ObjectType::WebScenario, ObjectAction::Skip),
+            (ObjectType::Trigger, ObjectAction::Update { id: "1".to_string(), changes: Map::new() }),
+        ]), &[]);
+
+        report.add_plan(get_plan(vec![
+            (ObjectType::Item, ObjectAction::Skip),
+            (ObjectType::WebScenario, ObjectAction::Create),
+        ]), &[(ObjectType::Item, "name".to_string()), (ObjectType::WebScenario, "name".to_string())]);
+
+        assert_eq!(report.items, ObjectCounts { created: 1, updated: 0, skipped: 1, failed: 0 });
+        assert_eq!(report.web_scenarios, ObjectCounts { created: 0, updated: 0, skipped: 1, failed: 1 });
+        assert_eq!(report.triggers, ObjectCounts { created: 0, updated: 1, skipped: 0, failed: 0 });
+        assert_eq!(report.plan.actions.len(), 5);
+    }
+
+    #[test]
+    fn report_should_be_rendered_as_table_and_json() {
+        let mut report = Report::default();
+
+        report.add_plan(get_plan(vec![(ObjectType::Item, ObjectAction::Create)]), &[]);
+        report.unresolved.push(UnresolvedUrl {
+            host: "srv2".to_string(),
+            url: "https://b.com".to_string(),
+        });
+
+        let text = report.to_string();
+
+        assert!(text.contains("OBJECT          CREATED  UPDATED  SKIPPED   FAILED"), "{text}");
+        assert!(text.contains("item                  1        0        0        0"), "{text}");
+        assert!(text.contains("- host 'srv2', url 'https://b.com'"), "{text}");
+
+        let json: serde_json::Value = serde_json::from_str(&report.to_json().unwrap()).unwrap();
+
+        assert_eq!(json["items"]["created"], 1);
+        assert_eq!(json["web-scenarios"]["failed"], 0);
+        assert_eq!(json["unresolved"][0]["host"], "srv2");
+        assert_eq!(json["invalid"], json!([]));
+        assert_eq!(json["failures"], json!([]));
+        assert!(json.get("plan").is_none());
+    }
+
+    #[test]
+    fn api_errors_should_take_precedence_over_partial_failure() {
+        let mut report = Report::default();
+
+        report.unresolved.push(UnresolvedUrl {
+            host: "srv2".to_string(),
+            url: "https://b.com".to_string(),
+        });
+
+        assert!(report.get_error(false).is_none());
+        assert!(matches!(report.get_error(true), Some(AppError::PartialFailure { failed: 0, unresolved: 1 })));
+
+        report.failures.push(UrlFailure::new("srv1", "https://a.com", &anyhow!("connection reset")));
+
+        assert!(matches!(report.get_error(false), Some(AppError::PartialFailure { failed: 1, unresolved: 0 })));
+
+        let api_error = anyhow::Error::from(AppError::Api(anyhow!("invalid expression")));
+        report.failures.push(UrlFailure::new("srv1", "https://c.com", &api_error.context("unable to create trigger")));
+
+        assert!(report.failures[1].api_error);
+        assert!(matches!(report.get_error(false), Some(AppError::Api(_))));
+    }
+}
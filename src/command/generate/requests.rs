@@ -0,0 +1,546 @@
+use std::collections::HashMap;
+
+use anyhow::anyhow;
+use zabbix_api::host::model::ZabbixHostTag;
+
+use crate::config::item::ZabbixItemConfig;
+use crate::config::trigger::ZabbixTriggerConfig;
+use crate::config::ws::{HttpField, StepMethod, WebScenarioConfig, WebScenarioStepConfig};
+use crate::source::UrlSource;
+use crate::template::{process_template_string, SCENARIO_NAME_TEMPLATE_VAR};
+use crate::types::OperationResult;
+use crate::zabbix::item::CreateItemRequest;
+use crate::zabbix::trigger::{CreateTriggerRequest, TriggerDependency};
+use crate::zabbix::webscenario::{CreateWebScenarioRequest, WebScenarioStep};
+
+const RETRIEVE_MODE_BODY: u8 = 0;
+
+/// Zabbix retrieves headers only with HEAD request
+const RETRIEVE_MODE_HEADERS: u8 = 1;
+
+const RETRIEVE_MODE_BOTH: u8 = 2;
+
+pub fn get_item_request(
+    item_config: &ZabbixItemConfig,
+    host_id: &str,
+    interface_id: &str,
+    template_vars: &HashMap<String, String>,
+) -> CreateItemRequest {
+    CreateItemRequest {
+        name: process_template_string(&item_config.name_template, template_vars),
+        key_: process_template_string(&item_config.key_template, template_vars),
+        host_id: host_id.to_string(),
+        r#type: item_config.r#type,
+        value_type: item_config.value_type,
+        interface_id: interface_id.to_string(),
+        tags: item_config.tags.clone(),
+        delay: item_config.delay.to_string(),
+    }
+}
+
+/// Steps from url source replace `scenario.steps`. Single step with source url is used
+/// when no steps are defined.
+pub fn get_web_scenario_request(
+    web_scenario_config: &WebScenarioConfig,
+    host_id: &str,
+    url_source: &UrlSource,
+    template_vars: &HashMap<String, String>,
+) -> OperationResult<CreateWebScenarioRequest> {
+    let overrides = &url_source.overrides;
+
+    let name_template = overrides.name.as_ref().unwrap_or(&web_scenario_config.name_template);
+    let scenario_name = process_template_string(name_template, template_vars);
+
+    let mut template_vars = template_vars.clone();
+    template_vars.insert(SCENARIO_NAME_TEMPLATE_VAR.to_string(), scenario_name.to_string());
+    let template_vars = &template_vars;
+
+    let mut headers = web_scenario_config.headers.clone();
+    headers.extend(overrides.headers.iter().cloned());
+
+    let status_codes = overrides
+        .expect_status_code
+        .clone()
+        .unwrap_or(web_scenario_config.expect_status_code.to_string());
+
+    let required_string = process_template_string(
+        overrides.required_string.as_ref().unwrap_or(&web_scenario_config.required_string),
+        template_vars,
+    );
+
+    let required_headers = if overrides.required_headers.is_empty() {
+        process_http_fields(&web_scenario_config.required_headers, template_vars)
+    } else {
+        process_http_fields(&overrides.required_headers, template_vars)
+    };
+
+    let default_checks = ResponseChecks { status_codes, required_string, required_headers };
+
+    let step_configs = if overrides.steps.is_empty() {
+        &web_scenario_config.steps
+    } else {
+        &overrides.steps
+    };
+
+    let steps = if step_configs.is_empty() {
+        vec![WebScenarioStep {
+            name: scenario_name.to_string(),
+            no: 1,
+            url: url_source.url.to_string(),
+            timeout: web_scenario_config.response_timeout.to_string(),
+            status_codes: default_checks.status_codes.to_string(),
+            required: default_checks.get_required_pattern(),
+            posts: String::new(),
+            variables: vec![],
+            follow_redirects: web_scenario_config.follow_redirects,
+            retrieve_mode: default_checks.get_retrieve_mode(web_scenario_config.retrieve_mode),
+        }]
+    } else {
+        let mut steps: Vec<WebScenarioStep> = vec![];
+
+        for (index, step_config) in step_configs.iter().enumerate() {
+            steps.push(get_web_scenario_step(
+                web_scenario_config,
+                step_config,
+                index as u32 + 1,
+                &default_checks,
+                template_vars,
+            )?);
+        }
+
+        steps
+    };
+
+    Ok(CreateWebScenarioRequest {
+        name: scenario_name,
+        host_id: host_id.to_string(),
+        delay: overrides
+            .update_interval
+            .clone()
+            .unwrap_or(web_scenario_config.update_interval.to_string()),
+        retries: web_scenario_config.attempts,
+        agent: web_scenario_config.agent.to_string(),
+        http_proxy: process_template_string(&web_scenario_config.http_proxy, template_vars),
+        headers: process_http_fields(&headers, template_vars),
+        variables: process_http_fields(&web_scenario_config.variables, template_vars),
+        steps,
+        tags: process_tags(&web_scenario_config.tags, template_vars),
+    })
+}
+
+fn get_web_scenario_step(
+    web_scenario_config: &WebScenarioConfig,
+    step_config: &WebScenarioStepConfig,
+    no: u32,
+    default_checks: &ResponseChecks,
+    template_vars: &HashMap<String, String>,
+) -> OperationResult<WebScenarioStep> {
+    let name = process_template_string(&step_config.name, template_vars);
+    let posts = process_template_string(&step_config.posts, template_vars);
+
+    if (step_config.method == StepMethod::Post) == posts.is_empty() {
+        return Err(anyhow!(
+            "step '{name}': posts are required for post method and not allowed for {} method",
+            step_config.method
+        ));
+    }
+
+    let checks = ResponseChecks {
+        status_codes: if step_config.status_codes.is_empty() {
+            default_checks.status_codes.to_string()
+        } else {
+            process_template_string(&step_config.status_codes, template_vars)
+        },
+        // HEAD response has no body, scenario `required-string` isn't applied
+        required_string: if step_config.method == StepMethod::Head {
+            String::new()
+        } else if step_config.required_string.is_empty() {
+            default_checks.required_string.to_string()
+        } else {
+            process_template_string(&step_config.required_string, template_vars)
+        },
+        required_headers: if step_config.required_headers.is_empty() {
+            default_checks.required_headers.clone()
+        } else {
+            process_http_fields(&step_config.required_headers, template_vars)
+        },
+    };
+
+    let retrieve_mode = match step_config.method {
+        StepMethod::Head => RETRIEVE_MODE_HEADERS,
+        _ => checks.get_retrieve_mode(web_scenario_config.retrieve_mode),
+    };
+
+    Ok(WebScenarioStep {
+        name,
+        no,
+        url: process_template_string(&step_config.url, template_vars),
+        timeout: web_scenario_config.response_timeout.to_string(),
+        required: checks.get_required_pattern(),
+        status_codes: checks.status_codes,
+        posts,
+        variables: process_http_fields(&step_config.variables, template_vars),
+        follow_redirects: web_scenario_config.follow_redirects,
+        retrieve_mode,
+    })
+}
+
+/// Rendered success criteria of web scenario step
+struct ResponseChecks {
+    status_codes: String,
+
+    /// Regular expression
+    required_string: String,
+
+    /// Header name and value regular expression
+    required_headers: Vec<HttpField>,
+}
+
+impl ResponseChecks {
+    /// Zabbix supports single `required` pattern per step, so header checks are combined with
+    /// required string by lookahead assertions: `^(?=(?s:.*?)(?:body))(?=(?s:.*?)(?mi:^Name:\s*value))`
+    fn get_required_pattern(&self) -> String {
+        let mut patterns: Vec<String> = vec![];
+
+        if !self.required_string.is_empty() {
+            patterns.push(self.required_string.to_string());
+        }
+
+        for header in &self.required_headers {
+            patterns.push(format!("(?mi:^{}:\\s*{})", regex::escape(&header.name), header.value));
+        }
+
+        if patterns.len() < 2 {
+            return patterns.pop().unwrap_or_default();
+        }
+
+        let lookaheads: Vec<String> = patterns
+            .iter()
+            .map(|pattern| format!("(?=(?s:.*?)(?:{pattern}))"))
+            .collect();
+
+        format!("^{}", lookaheads.join(""))
+    }
+
+    /// Headers must be retrieved to check them
+    fn get_retrieve_mode(&self, retrieve_mode: u8) -> u8 {
+        if !self.required_headers.is_empty() && retrieve_mode == RETRIEVE_MODE_BODY {
+            RETRIEVE_MODE_BOTH
+        } else {
+            retrieve_mode
+        }
+    }
+}
+
+/// Url source tags are added to `trigger.tags`
+pub fn get_trigger_request(
+    trigger_config: &ZabbixTriggerConfig,
+    url_source: &UrlSource,
+    dependencies: Vec<TriggerDependency>,
+    template_vars: &HashMap<String, String>,
+) -> CreateTriggerRequest {
+    let mut recovery_mode: Option<u8> = None;
+    let mut recovery_expression: Option<String> = None;
+
+    if !trigger_config.recovery_expression.is_empty() {
+        recovery_mode = Some(trigger_config.recovery_mode);
+        recovery_expression = Some(process_template_string(
+            &trigger_config.recovery_expression,
+            template_vars,
+        ));
+    }
+
+    let mut url: Option<String> = None;
+
+    if !trigger_config.url.is_empty() {
+        url = Some(process_template_string(&trigger_config.url, template_vars));
+    }
+
+    let mut event_name: Option<String> = None;
+
+    if !trigger_config.event_name.is_empty() {
+        event_name = Some(process_template_string(
+            &trigger_config.event_name,
+            template_vars,
+        ));
+    }
+
+    let mut tags = process_tags(&trigger_config.tags, template_vars);
+    tags.extend(url_source.overrides.tags.iter().cloned());
+
+    CreateTriggerRequest {
+        description: process_template_string(&trigger_config.name, template_vars),
+        expression: process_template_string(&trigger_config.problem_expression, template_vars),
+        priority: url_source
+            .overrides
+            .trigger_priority
+            .unwrap_or(trigger_config.priority),
+        recovery_mode,
+        recovery_expression,
+        url,
+        event_name,
+        dependencies,
+        tags,
+    }
+}
+
+fn process_tags(tags: &[ZabbixHostTag], template_vars: &HashMap<String, String>) -> Vec<ZabbixHostTag> {
+    tags.iter()
+        .map(|tag| ZabbixHostTag {
+            tag: tag.tag.to_string(),
+            value: process_template_string(&tag.value, template_vars),
+        })
+        .collect()
+}
+
+fn process_http_fields(
+    fields: &[HttpField],
+    template_vars: &HashMap<String, String>,
+) -> Vec<HttpField> {
+    fields
+        .iter()
+        .map(|field| HttpField {
+            name: field.name.to_string(),
+            value: process_template_string(&field.value, template_vars),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use zabbix_api::host::model::ZabbixHostTag;
+
+    use crate::command::generate::requests::{get_trigger_request, get_web_scenario_request};
+    use crate::config::trigger::ZabbixTriggerConfig;
+    use crate::config::ws::{HttpField, StepMethod, WebScenarioConfig, WebScenarioStepConfig};
+    use crate::source::{UrlSource, UrlSourceOverrides};
+    use crate::template::get_template_vars;
+    use crate::zabbix::trigger::TriggerDependency;
+
+    fn get_web_scenario_config(steps: Vec<WebScenarioStepConfig>) -> WebScenarioConfig {
+        WebScenarioConfig {
+            key_starts_with: "Check '".to_string(),
+            name_template: "Check '${URL}'".to_string(),
+            response_timeout: "15s".to_string(),
+            expect_status_code: "200".to_string(),
+            required_string: String::new(),
+            required_headers: vec![],
+            attempts: 3,
+            update_interval: "5m".to_string(),
+            follow_redirects: 1,
+            retrieve_mode: 0,
+            agent: String::new(),
+            http_proxy: String::new(),
+            headers: vec![],
+            variables: vec![],
+            tags: vec![],
+            steps,
+        }
+    }
+
+    fn get_step_config(name: &str, url: &str, method: StepMethod, posts: &str) -> WebScenarioStepConfig {
+        WebScenarioStepConfig {
+            name: name.to_string(),
+            url: url.to_string(),
+            method,
+            posts: posts.to_string(),
+            required_string: String::new(),
+            required_headers: vec![],
+            status_codes: String::new(),
+            variables: vec![],
+        }
+    }
+
+    #[test]
+    fn steps_should_be_rendered_in_order() {
+        let mut login_step = get_step_config(
+            "Login to ${URL_WITHOUT_PROTOCOL}", "${URL}/login", StepMethod::Post, "user={user}",
+        );
+        login_step.variables = vec![HttpField {
+            name: "{token}".to_string(),
+            value: "regex:token=([0-9a-z]+)".to_string(),
+        }];
+
+        let mut health_step =
+            get_step_config("${SCENARIO_NAME} health", "${URL}/health?token={token}", StepMethod::Get, "");
+        health_step.status_codes = "200,204".to_string();
+
+        let config = get_web_scenario_config(vec![
+            login_step,
+            get_step_config("Dashboard", "${URL}/dashboard", StepMethod::Head, ""),
+            health_step,
+        ]);
+
+        let url_source = UrlSource {
+            zabbix_host: "srv1".to_string(),
+            url: "https://app.company.com".to_string(),
+            ..Default::default()
+        };
+
+        let template_vars = get_template_vars("srv1", &url_source.url);
+
+        let request = get_web_scenario_request(&config, "10001", &url_source, &template_vars).unwrap();
+
+        assert_eq!(request.steps.len(), 3);
+
+        assert_eq!(request.steps[0].no, 1);
+        assert_eq!(request.steps[0].name, "Login to app.company.com");
+        assert_eq!(request.steps[0].url, "https://app.company.com/login");
+        assert_eq!(request.steps[0].posts, "user={user}");
+        assert_eq!(request.steps[0].variables[0].value, "regex:token=([0-9a-z]+)");
+        assert_eq!(request.steps[0].status_codes, "200");
+
+        assert_eq!(request.steps[1].no, 2);
+        assert_eq!(request.steps[1].retrieve_mode, 1);
+
+        assert_eq!(request.steps[2].no, 3);
+        assert_eq!(request.steps[2].name, "Check 'https://app.company.com' health");
+        assert_eq!(request.steps[2].url, "https://app.company.com/health?token={token}");
+        assert_eq!(request.steps[2].status_codes, "200,204");
+    }
+
+    #[test]
+    fn url_source_steps_should_replace_config_steps() {
+        let config = get_web_scenario_config(vec![
+            get_step_config("Index", "${URL}", StepMethod::Get, ""),
+        ]);
+
+        let url_source = UrlSource {
+            zabbix_host: "srv1".to_string(),
+            host_id: None,
+            url: "https://app.company.com".to_string(),
+            overrides: UrlSourceOverrides {
+                steps: vec![get_step_config("Api", "${URL}/api", StepMethod::Post, "a=1")],
+                ..Default::default()
+            },
+        };
+
+        let template_vars = get_template_vars("srv1", &url_source.url);
+
+        let request = get_web_scenario_request(&config, "10001", &url_source, &template_vars).unwrap();
+
+        assert_eq!(request.steps.len(), 1);
+        assert_eq!(request.steps[0].name, "Api");
+        assert_eq!(request.steps[0].url, "https://app.company.com/api");
+    }
+
+    #[test]
+    fn required_string_and_headers_should_be_combined() {
+        let mut config = get_web_scenario_config(vec![]);
+        config.required_string = "Welcome to ${URL_WITHOUT_PROTOCOL}".to_string();
+
+        let url_source = UrlSource {
+            zabbix_host: "srv1".to_string(),
+            url: "https://app.company.com".to_string(),
+            ..Default::default()
+        };
+
+        let template_vars = get_template_vars("srv1", &url_source.url);
+
+        let request = get_web_scenario_request(&config, "10001", &url_source, &template_vars).unwrap();
+
+        assert_eq!(request.steps[0].required, "Welcome to app.company.com");
+        assert_eq!(request.steps[0].retrieve_mode, 0);
+
+        config.required_headers = vec![HttpField {
+            name: "Content-Type".to_string(),
+            value: "text/html".to_string(),
+        }];
+
+        let request = get_web_scenario_request(&config, "10001", &url_source, &template_vars).unwrap();
+        let required = &request.steps[0].required;
+
+        assert_eq!(request.steps[0].retrieve_mode, 2);
+
+        assert_eq!(
+            required,
+            "^(?=(?s:.*?)(?:Welcome to app.company.com))(?=(?s:.*?)(?:(?mi:^Content\\-Type:\\s*text/html)))"
+        );
+    }
+
+    #[test]
+    fn head_step_should_check_headers_only() {
+        let mut config = get_web_scenario_config(vec![
+            get_step_config("Index", "${URL}", StepMethod::Head, ""),
+        ]);
+        config.required_string = "</html>".to_string();
+        config.required_headers = vec![HttpField {
+            name: "Content-Type".to_string(),
+            value: "text/html".to_string(),
+        }];
+
+        let url_source = UrlSource {
+            zabbix_host: "srv1".to_string(),
+            url: "https://app.company.com".to_string(),
+            ..Default::default()
+        };
+
+        let template_vars = get_template_vars("srv1", &url_source.url);
+
+        let request = get_web_scenario_request(&config, "10001", &url_source, &template_vars).unwrap();
+
+        assert_eq!(request.steps[0].retrieve_mode, 1);
+        assert_eq!(request.steps[0].required, "(?mi:^Content\\-Type:\\s*text/html)");
+
+        let mut step = get_step_config("Index", "${URL}", StepMethod::Head, "");
+        step.required_string = "ok".to_string();
+
+        assert!(step.validate().is_err());
+    }
+
+    #[test]
+    fn posts_should_match_step_method() {
+        let url_source = UrlSource {
+            zabbix_host: "srv1".to_string(),
+            url: "https://app.company.com".to_string(),
+            ..Default::default()
+        };
+
+        let template_vars = get_template_vars("srv1", &url_source.url);
+
+        for step in [
+            get_step_config("Get", "${URL}", StepMethod::Get, "a=1"),
+            get_step_config("Post", "${URL}", StepMethod::Post, ""),
+        ] {
+            let config = get_web_scenario_config(vec![step]);
+            assert!(get_web_scenario_request(&config, "10001", &url_source, &template_vars).is_err());
+        }
+    }
+
+    #[test]
+    fn trigger_tags_should_be_rendered_and_merged_with_url_source_tags() {
+        let config = ZabbixTriggerConfig {
+            name: "Site '${URL}' is unavailable".to_string(),
+            priority: 4,
+            problem_expression: "avg(/${HOST}/web.test.fail[${URL}],#3)>=1".to_string(),
+            recovery_mode: 0,
+            recovery_expression: String::new(),
+            event_name: String::new(),
+            url: String::new(),
+            tags: vec![ZabbixHostTag { tag: "host".to_string(), value: "${HOST}".to_string() }],
+            dependencies: vec![],
+        };
+
+        let url_source = UrlSource {
+            zabbix_host: "srv1".to_string(),
+            host_id: None,
+            url: "https://app.company.com".to_string(),
+            overrides: UrlSourceOverrides {
+                tags: vec![ZabbixHostTag { tag: "env".to_string(), value: "prod".to_string() }],
+                ..Default::default()
+            },
+        };
+
+        let template_vars = get_template_vars("srv1", &url_source.url);
+
+        let dependencies = vec![TriggerDependency { trigger_id: "100".to_string() }];
+
+        let request = get_trigger_request(&config, &url_source, dependencies.clone(), &template_vars);
+
+        assert_eq!(request.tags, vec![
+            ZabbixHostTag { tag: "host".to_string(), value: "srv1".to_string() },
+            ZabbixHostTag { tag: "env".to_string(), value: "prod".to_string() },
+        ]);
+        assert_eq!(request.dependencies, dependencies);
+    }
+}
@@ -0,0 +1,136 @@
+use std::collections::BTreeMap;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::mpsc;
+use std::thread;
+
+/// Process `inputs` with `concurrency` worker threads. Results are passed to `on_result`
+/// in the same order as inputs, so output of different inputs isn't mixed up.
+///
+/// Workers stop taking new inputs when `on_result` returns `false`. Inputs which were already
+/// taken are finished, their results are passed to `on_result` as well, its return value is ignored then.
+pub fn process_ordered<T, R>(
+    inputs: &[T],
+    concurrency: usize,
+    process: impl Fn(&T) -> R + Sync,
+    mut on_result: impl FnMut(&T, R) -> bool,
+) where
+    T: Sync,
+    R: Send,
+{
+    let next_index = AtomicUsize::new(0);
+    let stopped = AtomicBool::new(false);
+
+    thread::scope(|scope| {
+        let (sender, receiver) = mpsc::channel::<(usize, R)>();
+
+        for _ in 0..concurrency.clamp(1, inputs.len().max(1)) {
+            let sender = sender.clone();
+            let next_index = &next_index;
+            let stopped = &stopped;
+            let process = &process;
+
+            scope.spawn(move || loop {
+                if stopped.load(Ordering::SeqCst) {
+                    break;
+                }
+
+                let index = next_index.fetch_add(1, Ordering::SeqCst);
+
+                match inputs.get(index) {
+                    Some(input) => {
+                        if sender.send((index, process(input))).is_err() {
+                            break;
+                        }
+                    }
+                    None => break,
+                }
+            });
+        }
+
+        drop(sender);
+
+        let mut pending: BTreeMap<usize, R> = BTreeMap::new();
+        let mut expected_index = 0;
+
+        // Inputs are taken in order, so there are no gaps among received results
+        for (index, result) in receiver {
+            pending.insert(index, result);
+
+            while let Some(result) = pending.remove(&expected_index) {
+                if !on_result(&inputs[expected_index], result) {
+                    stopped.store(true, Ordering::SeqCst);
+                }
+
+                expected_index += 1;
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
+    use std::thread;
+    use std::time::Duration;
+
+    use crate::command::generate::worker::process_ordered;
+
+    #[test]
+    fn results_should_be_returned_in_input_order() {
+        let inputs: Vec<u64> = (0..20).collect();
+        let mut results: Vec<u64> = vec![];
+
+        process_ordered(
+            &inputs,
+            4,
+            |input| {
+                thread::sleep(Duration::from_millis((20 - input) % 7));
+                input * 10
+            },
+            |_, result| {
+                results.push(result);
+                true
+            },
+        );
+
+        assert_eq!(results, inputs.iter().map(|i| i * 10).collect::<Vec<u64>>());
+    }
+
+    #[test]
+    fn processing_should_stop_when_requested() {
+        let inputs: Vec<u64> = (0..100).collect();
+        let processed: Mutex<Vec<u64>> = Mutex::new(vec![]);
+        let mut results: Vec<u64> = vec![];
+
+        process_ordered(
+            &inputs,
+            4,
+            |input| {
+                // Later inputs are finished before the stop request
+                if *input == 5 {
+                    while !processed.lock().unwrap().contains(&9) {
+                        thread::sleep(Duration::from_millis(1));
+                    }
+                } else {
+                    thread::sleep(Duration::from_millis(1));
+                }
+
+                processed.lock().unwrap().push(*input);
+                *input
+            },
+            |_, result| {
+                results.push(result);
+                result < 5
+            },
+        );
+
+        let mut processed = processed.into_inner().unwrap();
+        processed.sort();
+
+        assert!(processed.len() < inputs.len());
+        assert!(processed.contains(&9));
+
+        // Every processed input is passed to `on_result`
+        assert_eq!(results, processed);
+    }
+}
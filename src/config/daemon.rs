@@ -0,0 +1,28 @@
+use serde_derive::Deserialize;
+use std::fmt::{Display, Formatter};
+
+#[derive(PartialEq, Deserialize, Clone, Debug)]
+#[serde(rename_all = "kebab-case")]
+pub struct DaemonConfig {
+    /// Pause between generation runs, i.e. "5m". Overridden by `--interval` option
+    #[serde(default = "get_default_interval")]
+    pub interval: String,
+}
+
+impl Default for DaemonConfig {
+    fn default() -> Self {
+        DaemonConfig {
+            interval: get_default_interval(),
+        }
+    }
+}
+
+impl Display for DaemonConfig {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "interval: '{}'", self.interval)
+    }
+}
+
+fn get_default_interval() -> String {
+    "5m".to_string()
+}
@@ -0,0 +1,52 @@
+use std::time::Duration;
+
+use anyhow::{anyhow, Context};
+
+use crate::types::OperationResult;
+
+/// Parse duration in Zabbix time suffix format: 30s, 5m, 12h, 7d, 1w.
+/// Value without suffix is treated as seconds.
+pub fn parse_duration(value: &str) -> OperationResult<Duration> {
+    let value = value.trim();
+
+    let (number, multiplier) = match value.chars().last() {
+        Some('s') => (&value[..value.len() - 1], 1),
+        Some('m') => (&value[..value.len() - 1], 60),
+        Some('h') => (&value[..value.len() - 1], 60 * 60),
+        Some('d') => (&value[..value.len() - 1], 24 * 60 * 60),
+        Some('w') => (&value[..value.len() - 1], 7 * 24 * 60 * 60),
+        Some(_) => (value, 1),
+        None => return Err(anyhow!("empty duration value")),
+    };
+
+    let number = number
+        .parse::<u64>()
+        .context(format!("invalid duration value '{value}'"))?;
+
+    Ok(Duration::from_secs(number * multiplier))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use crate::config::duration::parse_duration;
+
+    #[test]
+    fn durations_with_suffixes_should_be_parsed() {
+        assert_eq!(parse_duration("0").unwrap(), Duration::from_secs(0));
+        assert_eq!(parse_duration("45").unwrap(), Duration::from_secs(45));
+        assert_eq!(parse_duration("30s").unwrap(), Duration::from_secs(30));
+        assert_eq!(parse_duration("5m").unwrap(), Duration::from_secs(300));
+        assert_eq!(parse_duration("12h").unwrap(), Duration::from_secs(43200));
+        assert_eq!(parse_duration("7d").unwrap(), Duration::from_secs(604800));
+        assert_eq!(parse_duration("1w").unwrap(), Duration::from_secs(604800));
+    }
+
+    #[test]
+    fn invalid_durations_should_be_rejected() {
+        assert!(parse_duration("").is_err());
+        assert!(parse_duration("5x").is_err());
+        assert!(parse_duration("m").is_err());
+    }
+}
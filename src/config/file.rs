@@ -1,30 +1,159 @@
 use crate::config::AppConfig;
-use crate::types::OperationResult;
+use crate::types::{AppError, AppResult, OperationResult};
 use anyhow::Context;
-use config::Config;
-use std::path::Path;
+use config::{Config, ConfigError, Map, Source, Value};
+use std::{env, fs};
+use std::path::{Path, PathBuf};
 
-pub fn load_config_from_file(file_path: &Path) -> OperationResult<AppConfig> {
+/// Named profiles section, i.e. `profiles.production`. Profile values override the rest of config.
+pub const PROFILES_KEY: &str = "profiles";
+
+/// Environment variables with this prefix override config values,
+/// i.e. `WSZL_ZABBIX__API__PASSWORD` overrides `zabbix.api.password`.
+pub const ENV_VAR_PREFIX: &str = "WSZL_";
+pub const ENV_VAR_SEPARATOR: &str = "__";
+
+/// Load config from file or directory. All `*.yml` files from directory are merged
+/// in alphabetical order, i.e. `conf.d/00-base.yml`, `conf.d/10-production.yml`.
+///
+/// Values are applied in order: config files, profile, environment variables.
+pub fn load_config_from_file(file_path: &Path, profile: Option<&str>) -> AppResult<AppConfig> {
+    load_config(file_path, profile, env::vars().collect()).map_err(AppError::Config)
+}
+
+fn load_config(
+    file_path: &Path,
+    profile: Option<&str>,
+    env_vars: Vec<(String, String)>,
+) -> OperationResult<AppConfig> {
     let file_path_str = format!("{}", file_path.display());
-    info!("loading config from file '{file_path_str}'");
+    info!("loading config from '{file_path_str}'");
+
+    let mut builder = Config::builder();
+
+    if file_path.is_dir() {
+        for path in get_config_file_paths(file_path)? {
+            debug!("- merge config file '{}'", path.display());
+            builder = builder.add_source(config::File::from(path));
+        }
+    } else {
+        builder = builder.add_source(config::File::with_name(&file_path_str));
+    }
+
+    let mut settings = builder.build()?;
+
+    if let Some(profile) = profile {
+        info!("apply config profile '{profile}'");
+
+        let profile_settings = settings
+            .get_table(&format!("{PROFILES_KEY}.{profile}"))
+            .context(format!("config profile '{profile}' wasn't found"))?;
+
+        settings = Config::builder()
+            .add_source(settings)
+            .add_source(ProfileSource { profile_settings })
+            .build()?;
+    }
+
+    let env_overrides = get_env_overrides(env_vars);
 
-    let settings = Config::builder()
-        .add_source(config::File::with_name(&file_path_str))
-        .build()?;
+    if !env_overrides.is_empty() {
+        let mut builder = Config::builder().add_source(settings);
 
-    let config = settings
+        for (key, value) in env_overrides {
+            info!("override config value '{key}' from environment");
+            builder = builder.set_override(key, value)?;
+        }
+
+        settings = builder.build()?;
+    }
+
+    let mut config = settings
         .try_deserialize::<AppConfig>()
         .context("unable to load config")?;
 
+    config.zabbix.api.load_secrets()?;
+
+    for step in &config.zabbix.scenario.steps {
+        step.validate().context("invalid scenario steps")?;
+    }
+
     info!("config loaded: {}", config);
 
     Ok(config)
 }
 
+/// Convert `WSZL_ZABBIX__TARGET_HOSTNAME` to `zabbix.target-hostname`
+fn get_env_overrides(env_vars: Vec<(String, String)>) -> Vec<(String, String)> {
+    let mut results: Vec<(String, String)> = env_vars
+        .into_iter()
+        .filter_map(|(name, value)| {
+            let path = name.strip_prefix(ENV_VAR_PREFIX)?;
+
+            if !path.contains(ENV_VAR_SEPARATOR) {
+                return None;
+            }
+
+            let key = path
+                .split(ENV_VAR_SEPARATOR)
+                .map(|part| part.to_lowercase().replace('_', "-"))
+                .collect::<Vec<String>>()
+                .join(".");
+
+            Some((key, value))
+        })
+        .collect();
+
+    results.sort();
+
+    results
+}
+
+fn get_config_file_paths(dir_path: &Path) -> OperationResult<Vec<PathBuf>> {
+    let mut results: Vec<PathBuf> = vec![];
+
+    for entry in fs::read_dir(dir_path).context("unable to read config directory")? {
+        let path = entry?.path();
+
+        let is_yaml = match path.extension().and_then(|ext| ext.to_str()) {
+            Some(ext) => ext == "yml" || ext == "yaml",
+            None => false,
+        };
+
+        if path.is_file() && is_yaml {
+            results.push(path);
+        }
+    }
+
+    results.sort();
+
+    Ok(results)
+}
+
+#[derive(Clone, Debug)]
+struct ProfileSource {
+    profile_settings: Map<String, Value>,
+}
+
+impl Source for ProfileSource {
+    fn clone_into_box(&self) -> Box<dyn Source + Send + Sync> {
+        Box::new(self.clone())
+    }
+
+    fn collect(&self) -> Result<Map<String, Value>, ConfigError> {
+        Ok(self.profile_settings.clone())
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::config::file::load_config_from_file;
-    use crate::config::item::ZabbixItemConfig;
+    use crate::config::file::{get_env_overrides, load_config, load_config_from_file};
+    use crate::config::daemon::DaemonConfig;
+    use crate::config::item::{InterfaceMode, ZabbixItemConfig};
+    use crate::config::logging::{LogFormat, LogRotationConfig, LogTarget, LoggingConfig};
+    use crate::config::prune::{PruneAction, PruneConfig};
+    use crate::config::trigger::TriggerDependencyConfig;
+    use crate::config::ws::HttpField;
     use crate::config::{
         AppConfig, WebScenarioConfig, ZabbixApiConfig, ZabbixConfig, ZabbixTriggerConfig,
     };
@@ -35,7 +164,7 @@ mod tests {
     fn complete_config_should_be_loaded_from_file() {
         let file_path = Path::new("test-data/wszl.yml");
 
-        match load_config_from_file(file_path) {
+        match load_config_from_file(file_path, None) {
             Ok(config) => {
                 let expected_config = AppConfig {
                     zabbix: ZabbixConfig {
@@ -45,12 +174,16 @@ mod tests {
                             endpoint: "http://zabbix/api_jsonrpc.php".to_string(),
                             username: "abcd".to_string(),
                             password: "0329jg02934jg34g".to_string(),
+                            password_file: String::new(),
+                            token: String::new(),
+                            token_file: String::new(),
                         },
 
-                        item: ZabbixItemConfig {
+                        item: Some(ZabbixItemConfig {
                             name_template: "Vhost '{}' item".to_string(),
                             key_template: "vhost.item[{}]".to_string(),
                             interface_id: "0".to_string(),
+                            interface: InterfaceMode::Fixed,
                             delay: "5m".to_string(),
                             r#type: 7,
                             value_type: 0,
@@ -58,9 +191,11 @@ mod tests {
                                 tag: "abc".to_string(),
                                 value: "something".to_string(),
                             }],
-                        },
+                        }),
 
-                        trigger: ZabbixTriggerConfig {
+                        items: vec![],
+
+                        trigger: Some(ZabbixTriggerConfig {
                             name: "Site '${URL}' is unavailable".to_string(),
                             priority: 4,
                             problem_expression: "avg(/${HOST}/web.test.fail[${URL}],#3)>=1"
@@ -70,17 +205,59 @@ mod tests {
                                 .to_string(),
                             event_name: "${URL} is down".to_string(),
                             url: "${URL}".to_string(),
-                        },
+                            tags: vec![ZabbixHostTag {
+                                tag: "url".to_string(),
+                                value: "${URL_WITHOUT_PROTOCOL}".to_string(),
+                            }],
+                            dependencies: vec![TriggerDependencyConfig {
+                                host: "${HOST}".to_string(),
+                                description: "Zabbix agent is not available".to_string(),
+                            }],
+                        }),
+
+                        triggers: vec![],
 
                         scenario: WebScenarioConfig {
                             key_starts_with: "blablabla".to_string(),
                             name_template: "Check index page '${URL}'".to_string(),
                             response_timeout: "15s".to_string(),
                             expect_status_code: "200".to_string(),
+                            required_string: String::new(),
+                            required_headers: vec![],
                             attempts: 3,
                             update_interval: "5m".to_string(),
+                            follow_redirects: 1,
+                            retrieve_mode: 0,
+                            agent: "wszl".to_string(),
+                            http_proxy: String::new(),
+                            headers: vec![HttpField {
+                                name: "Host".to_string(),
+                                value: "${URL_WITHOUT_PROTOCOL}".to_string(),
+                            }],
+                            variables: vec![],
+                            tags: vec![],
+                            steps: vec![],
+                        },
+
+                        prune: PruneConfig {
+                            action: PruneAction::Delete,
+                            grace_period: "7d".to_string(),
                         },
                     },
+
+                    logging: LoggingConfig {
+                        target: LogTarget::Stderr,
+                        file: "wszl.log".to_string(),
+                        format: LogFormat::Json,
+                        rotation: Some(LogRotationConfig {
+                            max_size: "10m".to_string(),
+                            keep: 5,
+                        }),
+                    },
+
+                    daemon: DaemonConfig {
+                        interval: "1m".to_string(),
+                    },
                 };
 
                 assert_eq!(config, expected_config);
@@ -92,4 +269,124 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn profile_values_should_override_config() {
+        let file_path = Path::new("test-data/profiles/wszl.yml");
+
+        let config = load_config_from_file(file_path, None).unwrap();
+
+        assert_eq!(config.zabbix.api.endpoint, "http://zabbix/api_jsonrpc.php");
+        assert_eq!(config.zabbix.api.username, "abcd");
+
+        let config = load_config_from_file(file_path, Some("staging")).unwrap();
+
+        assert_eq!(config.zabbix.api.endpoint, "http://zabbix-staging/api_jsonrpc.php");
+        assert_eq!(config.zabbix.api.username, "abcd");
+        assert_eq!(config.zabbix.target_hostname, "staging");
+        assert_eq!(config.zabbix.trigger.as_ref().unwrap().priority, 2);
+    }
+
+    #[test]
+    fn items_and_triggers_lists_should_be_loaded() {
+        let file_path = Path::new("test-data/lists/wszl.yml");
+
+        let config = load_config_from_file(file_path, None).unwrap();
+
+        assert!(config.zabbix.item.is_none());
+
+        let items = config.zabbix.get_items();
+
+        assert_eq!(items.len(), 2);
+        assert_eq!(items[1].key_template, "web.certificate.get[${URL_WITHOUT_PROTOCOL}]");
+        assert_eq!(items[0].interface, InterfaceMode::Fixed);
+        assert_eq!(items[1].interface, InterfaceMode::Auto);
+
+        assert!(config.zabbix.trigger.is_none());
+
+        let triggers = config.zabbix.get_triggers();
+
+        assert_eq!(triggers.len(), 2);
+        assert_eq!(triggers[0].priority, 4);
+        assert_eq!(triggers[1].name, "Site '${URL}' responds slowly");
+    }
+
+    #[test]
+    fn unknown_profile_should_be_rejected() {
+        let file_path = Path::new("test-data/profiles/wszl.yml");
+
+        assert!(load_config_from_file(file_path, Some("unknown")).is_err());
+    }
+
+    #[test]
+    fn config_files_from_directory_should_be_merged() {
+        let dir_path = Path::new("test-data/conf.d");
+
+        let config = load_config_from_file(dir_path, Some("production")).unwrap();
+
+        assert_eq!(config.zabbix.api.endpoint, "http://zabbix-production/api_jsonrpc.php");
+        assert_eq!(config.zabbix.api.username, "prod");
+        assert_eq!(config.zabbix.scenario.attempts, 3);
+    }
+
+    #[test]
+    fn env_vars_should_be_converted_to_config_keys() {
+        let env_vars = vec![
+            ("WSZL_ZABBIX__API__PASSWORD".to_string(), "secret".to_string()),
+            ("WSZL_ZABBIX__TARGET_HOSTNAME".to_string(), "srv1".to_string()),
+            ("WSZL_CONFIG".to_string(), "wszl.yml".to_string()),
+            ("HOME".to_string(), "/root".to_string()),
+        ];
+
+        let overrides = get_env_overrides(env_vars);
+
+        assert_eq!(overrides, vec![
+            ("zabbix.api.password".to_string(), "secret".to_string()),
+            ("zabbix.target-hostname".to_string(), "srv1".to_string()),
+        ]);
+    }
+
+    #[test]
+    fn env_vars_should_override_config_values() {
+        let file_path = Path::new("test-data/profiles/wszl.yml");
+
+        let env_vars = vec![
+            ("WSZL_ZABBIX__API__PASSWORD".to_string(), "from-env".to_string()),
+            ("WSZL_ZABBIX__TRIGGER__PRIORITY".to_string(), "5".to_string()),
+        ];
+
+        let config = load_config(file_path, Some("staging"), env_vars).unwrap();
+
+        assert_eq!(config.zabbix.api.password, "from-env");
+        assert_eq!(config.zabbix.api.endpoint, "http://zabbix-staging/api_jsonrpc.php");
+        assert_eq!(config.zabbix.trigger.as_ref().unwrap().priority, 5);
+    }
+
+    #[test]
+    fn password_should_be_read_from_file() {
+        let file_path = Path::new("test-data/wszl.yml");
+
+        let env_vars = vec![(
+            "WSZL_ZABBIX__API__PASSWORD_FILE".to_string(),
+            "test-data/secrets/zabbix-password".to_string(),
+        )];
+
+        let config = load_config(file_path, None, env_vars).unwrap();
+
+        assert_eq!(config.zabbix.api.password, "s3cr3t");
+    }
+
+    #[test]
+    fn token_should_be_read_from_file() {
+        let file_path = Path::new("test-data/wszl.yml");
+
+        let env_vars = vec![(
+            "WSZL_ZABBIX__API__TOKEN_FILE".to_string(),
+            "test-data/secrets/zabbix-token".to_string(),
+        )];
+
+        let config = load_config(file_path, None, env_vars).unwrap();
+
+        assert_eq!(config.zabbix.api.token, "b1d4f5e7c0a9");
+    }
 }
@@ -8,7 +8,15 @@ use zabbix_api::host::model::ZabbixHostTag;
 pub struct ZabbixItemConfig {
     pub name_template: String,
     pub key_template: String,
+
+    /// Used with `fixed` interface mode
+    #[serde(default = "get_default_interface_id")]
     pub interface_id: String,
+
+    /// `auto` - main host interface of the type matching the item type
+    #[serde(default)]
+    pub interface: InterfaceMode,
+
     pub delay: String,
 
     /// Item type:
@@ -22,13 +30,40 @@ pub struct ZabbixItemConfig {
     pub tags: Vec<ZabbixHostTag>,
 }
 
+#[derive(PartialEq, Deserialize, Clone, Copy, Default, Debug)]
+#[serde(rename_all = "kebab-case")]
+pub enum InterfaceMode {
+    /// `interface-id` value for all hosts
+    #[default]
+    Fixed,
+
+    /// Resolved for every host through Zabbix API
+    Auto,
+}
+
 impl Display for ZabbixItemConfig {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        write!(f, "name-template: '{}', key-template: '{}', interface-id: '{}', delay: '{}', type: {}, value-type: {}",
-               self.name_template, self.key_template, self.interface_id, self.delay, self.r#type, self.value_type)
+        write!(f, "name-template: '{}', key-template: '{}', interface-id: '{}', interface: {}, delay: '{}', type: {}, value-type: {}",
+               self.name_template, self.key_template, self.interface_id, self.interface,
+               self.delay, self.r#type, self.value_type)
     }
 }
 
+impl Display for InterfaceMode {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let value = match self {
+            InterfaceMode::Fixed => "fixed",
+            InterfaceMode::Auto => "auto",
+        };
+
+        write!(f, "{value}")
+    }
+}
+
+fn get_default_interface_id() -> String {
+    "0".to_string()
+}
+
 fn get_empty_tag_vec() -> Vec<ZabbixHostTag> {
     Vec::new()
 }
@@ -0,0 +1,83 @@
+use std::fmt::{Display, Formatter};
+
+use serde_derive::Deserialize;
+
+#[derive(PartialEq, Deserialize, Clone, Debug)]
+#[serde(rename_all = "kebab-case")]
+pub struct LoggingConfig {
+    #[serde(default)]
+    pub target: LogTarget,
+
+    /// Log file path, `file` target only
+    #[serde(default = "get_default_log_file")]
+    pub file: String,
+
+    #[serde(default)]
+    pub format: LogFormat,
+
+    /// Rotate log file by size, `file` target only. Log file grows without limit by default
+    #[serde(default)]
+    pub rotation: Option<LogRotationConfig>,
+}
+
+#[derive(PartialEq, Deserialize, Clone, Copy, Debug, Default)]
+#[serde(rename_all = "kebab-case")]
+pub enum LogTarget {
+    #[default]
+    File,
+    Stdout,
+    Stderr,
+    /// Local syslog socket `/dev/log`, unix only
+    Syslog,
+}
+
+#[derive(PartialEq, Deserialize, Clone, Copy, Debug, Default)]
+#[serde(rename_all = "kebab-case")]
+pub enum LogFormat {
+    #[default]
+    Text,
+    /// JSON lines, host and url of generation records are in `mdc` field
+    Json,
+}
+
+#[derive(PartialEq, Deserialize, Clone, Debug)]
+#[serde(rename_all = "kebab-case")]
+pub struct LogRotationConfig {
+    /// Log file size which triggers rotation, i.e. "10m". Supported suffixes: k, m, g
+    pub max_size: String,
+
+    /// Rotated files to keep: `wszl.log.0` is the latest. Zero means rotated file is deleted
+    #[serde(default = "get_default_keep")]
+    pub keep: u32,
+}
+
+impl Default for LoggingConfig {
+    fn default() -> Self {
+        LoggingConfig {
+            target: LogTarget::default(),
+            file: get_default_log_file(),
+            format: LogFormat::default(),
+            rotation: None,
+        }
+    }
+}
+
+impl Display for LoggingConfig {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "target: {:?}, file: '{}', format: {:?}", self.target, self.file, self.format)?;
+
+        if let Some(rotation) = &self.rotation {
+            write!(f, ", rotation: max-size '{}', keep {}", rotation.max_size, rotation.keep)?;
+        }
+
+        Ok(())
+    }
+}
+
+fn get_default_log_file() -> String {
+    "wszl.log".to_string()
+}
+
+fn get_default_keep() -> u32 {
+    5
+}
@@ -1,24 +1,44 @@
 pub mod item;
+pub mod daemon;
+pub mod duration;
 pub mod file;
+pub mod logging;
+pub mod prune;
+pub mod size;
 pub mod trigger;
 pub mod ws;
 
 use std::fmt::{Display, Formatter};
+use std::fs;
 
+use anyhow::{anyhow, Context};
+
+use crate::config::daemon::DaemonConfig;
 use crate::config::item::ZabbixItemConfig;
+use crate::config::logging::LoggingConfig;
+use crate::config::prune::PruneConfig;
 use crate::config::trigger::ZabbixTriggerConfig;
 use crate::config::ws::WebScenarioConfig;
+use crate::types::{EmptyResult, OperationResult};
 use serde::Deserialize;
 
 #[derive(PartialEq, Deserialize, Clone, Debug)]
 #[serde(rename_all = "kebab-case")]
 pub struct AppConfig {
-    pub zabbix: ZabbixConfig
+    pub zabbix: ZabbixConfig,
+
+    /// Log target, format and rotation. CLI options override these values
+    #[serde(default)]
+    pub logging: LoggingConfig,
+
+    /// Used with `daemon` command
+    #[serde(default)]
+    pub daemon: DaemonConfig
 }
 
 impl Display for AppConfig {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}", self.zabbix)
+        write!(f, "{}, logging: '{}', daemon: '{}'", self.zabbix, self.logging, self.daemon)
     }
 }
 
@@ -32,17 +52,55 @@ pub struct ZabbixConfig {
 
     pub api: ZabbixApiConfig,
 
-    pub item: ZabbixItemConfig,
+    /// Single item, kept for compatibility. Optional, use `items` for several items per url
+    #[serde(default)]
+    pub item: Option<ZabbixItemConfig>,
+
+    /// Zero or more items per url
+    #[serde(default)]
+    pub items: Vec<ZabbixItemConfig>,
+
+    /// Single trigger, kept for compatibility. Use `triggers` for several triggers per url
+    #[serde(default)]
+    pub trigger: Option<ZabbixTriggerConfig>,
+
+    /// Each trigger is created and checked independently
+    #[serde(default)]
+    pub triggers: Vec<ZabbixTriggerConfig>,
+
+    pub scenario: WebScenarioConfig,
+
+    /// Used with `--prune` option
+    #[serde(default)]
+    pub prune: PruneConfig
+}
 
-    pub trigger: ZabbixTriggerConfig,
+impl ZabbixConfig {
+    /// `item` followed by `items`
+    pub fn get_items(&self) -> Vec<&ZabbixItemConfig> {
+        self.item.iter().chain(self.items.iter()).collect()
+    }
 
-    pub scenario: WebScenarioConfig
+    /// `trigger` followed by `triggers`
+    pub fn get_triggers(&self) -> Vec<&ZabbixTriggerConfig> {
+        self.trigger.iter().chain(self.triggers.iter()).collect()
+    }
 }
 
 impl Display for ZabbixConfig {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        write!(f, "api: '{}', item: '{}', trigger: '{}', scenario: '{}'",
-           self.api, self.item, self.trigger, self.scenario
+        let items = self.get_items().iter()
+            .map(|item| format!("'{item}'"))
+            .collect::<Vec<String>>()
+            .join(", ");
+
+        let triggers = self.get_triggers().iter()
+            .map(|trigger| format!("'{trigger}'"))
+            .collect::<Vec<String>>()
+            .join(", ");
+
+        write!(f, "api: '{}', items: [{}], triggers: [{}], scenario: '{}', prune: '{}'",
+           self.api, items, triggers, self.scenario, self.prune
         )
     }
 }
@@ -51,16 +109,62 @@ impl Display for ZabbixConfig {
 #[serde(rename_all = "kebab-case")]
 pub struct ZabbixApiConfig {
     pub endpoint: String,
+
+    #[serde(default = "get_empty_string_value")]
     pub username: String,
-    pub password: String
+
+    #[serde(default = "get_empty_string_value")]
+    pub password: String,
+
+    /// Read password from file, i.e. docker/kubernetes secret. Overrides `password`
+    #[serde(default = "get_empty_string_value")]
+    pub password_file: String,
+
+    /// API token (Zabbix 5.4+). Used instead of username and password
+    #[serde(default = "get_empty_string_value")]
+    pub token: String,
+
+    /// Read API token from file. Overrides `token`
+    #[serde(default = "get_empty_string_value")]
+    pub token_file: String
+}
+
+impl ZabbixApiConfig {
+    pub fn load_secrets(&mut self) -> EmptyResult {
+        if !self.password_file.is_empty() {
+            info!("loading zabbix api password from file '{}'", self.password_file);
+            self.password = read_secret_file(&self.password_file)?;
+        }
+
+        if !self.token_file.is_empty() {
+            info!("loading zabbix api token from file '{}'", self.token_file);
+            self.token = read_secret_file(&self.token_file)?;
+        }
+
+        if self.token.is_empty() && self.username.is_empty() {
+            return Err(anyhow!("zabbix api token or username is required"));
+        }
+
+        Ok(())
+    }
 }
 
 impl Display for ZabbixApiConfig {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        write!(f, "endpoint '{}', username '{}', password '***********'", self.endpoint, self.username)
+        write!(f, "endpoint '{}', username '{}', password '***********', password-file '{}', \
+                   token '{}', token-file '{}'",
+               self.endpoint, self.username, self.password_file,
+               if self.token.is_empty() { "" } else { "***********" }, self.token_file)
     }
 }
 
+fn read_secret_file(file_path: &str) -> OperationResult<String> {
+    let content = fs::read_to_string(file_path)
+        .context(format!("unable to read secret file '{file_path}'"))?;
+
+    Ok(content.trim_end_matches(['\r', '\n']).to_string())
+}
+
 fn get_empty_string_value() -> String {
     String::new()
 }
\ No newline at end of file
@@ -0,0 +1,41 @@
+use serde_derive::Deserialize;
+use std::fmt::{Display, Formatter};
+
+#[derive(PartialEq, Deserialize, Clone, Debug)]
+#[serde(rename_all = "kebab-case")]
+pub struct PruneConfig {
+    #[serde(default)]
+    pub action: PruneAction,
+
+    /// How long orphaned objects stay disabled before deletion, i.e. "7d".
+    /// Applies to `delete` action only.
+    #[serde(default = "get_default_grace_period")]
+    pub grace_period: String,
+}
+
+#[derive(PartialEq, Deserialize, Clone, Copy, Debug, Default)]
+#[serde(rename_all = "kebab-case")]
+pub enum PruneAction {
+    #[default]
+    Disable,
+    Delete,
+}
+
+impl Default for PruneConfig {
+    fn default() -> Self {
+        PruneConfig {
+            action: PruneAction::default(),
+            grace_period: get_default_grace_period(),
+        }
+    }
+}
+
+impl Display for PruneConfig {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "action: {:?}, grace-period: '{}'", self.action, self.grace_period)
+    }
+}
+
+fn get_default_grace_period() -> String {
+    "0".to_string()
+}
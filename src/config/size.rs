@@ -0,0 +1,42 @@
+use anyhow::{anyhow, Context};
+
+use crate::types::OperationResult;
+
+/// Parse size in bytes with suffixes: 512k, 10m, 1g. Value without suffix is treated as bytes.
+pub fn parse_size(value: &str) -> OperationResult<u64> {
+    let value = value.trim();
+
+    let (number, multiplier) = match value.chars().last().map(|c| c.to_ascii_lowercase()) {
+        Some('k') => (&value[..value.len() - 1], 1024),
+        Some('m') => (&value[..value.len() - 1], 1024 * 1024),
+        Some('g') => (&value[..value.len() - 1], 1024 * 1024 * 1024),
+        Some(_) => (value, 1),
+        None => return Err(anyhow!("empty size value")),
+    };
+
+    let number = number
+        .parse::<u64>()
+        .context(format!("invalid size value '{value}'"))?;
+
+    Ok(number * multiplier)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::config::size::parse_size;
+
+    #[test]
+    fn sizes_with_suffixes_should_be_parsed() {
+        assert_eq!(parse_size("100").unwrap(), 100);
+        assert_eq!(parse_size("512k").unwrap(), 512 * 1024);
+        assert_eq!(parse_size("10m").unwrap(), 10 * 1024 * 1024);
+        assert_eq!(parse_size("1G").unwrap(), 1024 * 1024 * 1024);
+    }
+
+    #[test]
+    fn invalid_sizes_should_be_rejected() {
+        assert!(parse_size("").is_err());
+        assert!(parse_size("10x").is_err());
+        assert!(parse_size("m").is_err());
+    }
+}
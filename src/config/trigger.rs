@@ -1,24 +1,49 @@
 use serde_derive::Deserialize;
 use std::fmt::{Display, Formatter};
+use zabbix_api::host::model::ZabbixHostTag;
 
+/// Trigger severity: 0 - not classified, 5 - disaster
+pub const MAX_TRIGGER_PRIORITY: u8 = 5;
+
+/// Template variables are supported in all string values, tags and dependencies,
+/// see `template::get_template_vars`
 #[derive(PartialEq, Deserialize, Clone, Debug)]
 #[serde(rename_all = "kebab-case")]
 pub struct ZabbixTriggerConfig {
     pub name: String,
     pub priority: u8,
     pub problem_expression: String,
+    #[serde(default)]
     pub recovery_mode: u8,
+    #[serde(default)]
     pub recovery_expression: String,
+    #[serde(default)]
     pub event_name: String,
-    pub url: String
+    #[serde(default)]
+    pub url: String,
+
+    #[serde(default)]
+    pub tags: Vec<ZabbixHostTag>,
+
+    /// Triggers which suppress this trigger while they are in problem state,
+    /// i.e. host availability trigger. Resolved to trigger ids during generation
+    #[serde(default)]
+    pub dependencies: Vec<TriggerDependencyConfig>
+}
+
+#[derive(PartialEq, Deserialize, Clone, Debug)]
+#[serde(rename_all = "kebab-case")]
+pub struct TriggerDependencyConfig {
+    pub host: String,
+    pub description: String
 }
 
 impl Display for ZabbixTriggerConfig {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        write!(f, "name '{}', priority: {}, problem-expression: '{}', recovery-mode: {}, recovery-expression: '{}', event-name: '{}', url: '{}'",
+        write!(f, "name '{}', priority: {}, problem-expression: '{}', recovery-mode: {}, recovery-expression: '{}', event-name: '{}', url: '{}', tags: {}, dependencies: {}",
                self.name, self.priority, self.problem_expression,
                self.recovery_mode, self.recovery_expression,
-               self.event_name, self.url
+               self.event_name, self.url, self.tags.len(), self.dependencies.len()
         )
     }
 }
\ No newline at end of file
@@ -1,24 +1,171 @@
-use serde_derive::Deserialize;
+use anyhow::anyhow;
+use serde_derive::{Deserialize, Serialize};
 use std::fmt::{Display, Formatter};
+use zabbix_api::host::model::ZabbixHostTag;
 
+use crate::types::EmptyResult;
+
+/// Zabbix API: https://www.zabbix.com/documentation/6.0/en/manual/api/reference/httptest/object
+///
+/// Template variables are supported in string values except `response-timeout`, `expect-status-code`,
+/// `update-interval` and `agent`, see `template::get_template_vars`
 #[derive(PartialEq, Deserialize, Clone, Debug)]
 #[serde(rename_all = "kebab-case")]
 pub struct WebScenarioConfig {
+    /// Name prefix of web scenarios created by older versions without tags, i.e. "Check index page '".
+    /// Such web scenarios are pruned as well, empty value disables it
+    #[serde(default)]
     pub key_starts_with: String,
     pub name_template: String,
+
+    /// Step response timeout, i.e. "15s"
     pub response_timeout: String,
+
     pub expect_status_code: String,
+
+    /// Regular expression which response must contain
+    #[serde(default)]
+    pub required_string: String,
+
+    /// Response headers which must be present, value is a regular expression
+    #[serde(default)]
+    pub required_headers: Vec<HttpField>,
+
+    /// Number of attempts before the scenario fails (`retries`)
     pub attempts: u8,
-    pub update_interval: String
+
+    /// Scenario execution interval (`delay`)
+    pub update_interval: String,
+
+    /// Possible values:
+    /// 0 - don't follow redirects;
+    /// 1 - (default) follow redirects.
+    #[serde(default = "get_default_follow_redirects")]
+    pub follow_redirects: u8,
+
+    /// Possible values:
+    /// 0 - (default) body;
+    /// 1 - headers;
+    /// 2 - both body and headers.
+    #[serde(default)]
+    pub retrieve_mode: u8,
+
+    /// User agent string. Zabbix default is used when empty
+    #[serde(default)]
+    pub agent: String,
+
+    #[serde(default)]
+    pub http_proxy: String,
+
+    #[serde(default)]
+    pub headers: Vec<HttpField>,
+
+    #[serde(default)]
+    pub variables: Vec<HttpField>,
+
+    /// Tags `managed-by`, `wszl-source` and `wszl-url-hash` are added automatically
+    #[serde(default)]
+    pub tags: Vec<ZabbixHostTag>,
+
+    /// Scenario steps in execution order. Single step with url from source is used when empty
+    #[serde(default)]
+    pub steps: Vec<WebScenarioStepConfig>,
+}
+
+/// Zabbix API: https://www.zabbix.com/documentation/6.0/en/manual/api/reference/httptest/object#scenario-step
+///
+/// Template variables are supported in all string values, see `template::get_template_vars`
+#[derive(PartialEq, Deserialize, Clone, Debug)]
+#[serde(rename_all = "kebab-case")]
+pub struct WebScenarioStepConfig {
+    pub name: String,
+
+    /// i.e. "${URL}/login"
+    pub url: String,
+
+    #[serde(default)]
+    pub method: StepMethod,
+
+    /// Raw post data, i.e. "login={user}&password={password}". Requires `post` method
+    #[serde(default)]
+    pub posts: String,
+
+    /// Default: scenario `required-string` value
+    #[serde(default)]
+    pub required_string: String,
+
+    /// Default: scenario `required-headers` value
+    #[serde(default)]
+    pub required_headers: Vec<HttpField>,
+
+    /// Default: `expect-status-code` value
+    #[serde(default)]
+    pub status_codes: String,
+
+    /// Variables extracted from response, i.e. name: "{token}", value: "regex:token=([0-9a-z]+)"
+    #[serde(default)]
+    pub variables: Vec<HttpField>,
+}
+
+impl WebScenarioStepConfig {
+    /// HEAD response has no body, so `required-string` can't be checked
+    pub fn validate(&self) -> EmptyResult {
+        if self.method == StepMethod::Head && !self.required_string.is_empty() {
+            return Err(anyhow!(
+                "step '{}': required-string isn't allowed for head method, use required-headers",
+                self.name
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+/// Zabbix sends POST request when step has post data and HEAD request
+/// when only headers are retrieved (`retrieve_mode` 1)
+#[derive(PartialEq, Deserialize, Clone, Copy, Default, Debug)]
+#[serde(rename_all = "kebab-case")]
+pub enum StepMethod {
+    #[default]
+    Get,
+    Post,
+    Head,
+}
+
+impl Display for StepMethod {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let value = match self {
+            StepMethod::Get => "get",
+            StepMethod::Post => "post",
+            StepMethod::Head => "head",
+        };
+
+        write!(f, "{value}")
+    }
+}
+
+/// Header or variable of a web scenario
+#[derive(PartialEq, Serialize, Deserialize, Clone, Debug)]
+pub struct HttpField {
+    pub name: String,
+    pub value: String,
 }
 
 impl Display for WebScenarioConfig {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         write!(
             f, "key-starts-with: '{}', name-template: '{}', response-timeout: '{}', \
-            expect-status-code: '{}, attempts: {}, update-interval: '{}'",
+            expect-status-code: '{}, required-string: '{}', required-headers: {}, attempts: {}, \
+            update-interval: '{}', follow-redirects: {}, \
+            retrieve-mode: {}, agent: '{}', http-proxy: '{}', headers: {}, variables: {}, tags: {}, steps: {}",
             self.key_starts_with, self.name_template, self.response_timeout,
-            self.expect_status_code, self.attempts, self.update_interval
+            self.expect_status_code, self.required_string, self.required_headers.len(),
+            self.attempts, self.update_interval, self.follow_redirects, self.retrieve_mode, self.agent, self.http_proxy,
+            self.headers.len(), self.variables.len(), self.tags.len(), self.steps.len()
         )
     }
-}
\ No newline at end of file
+}
+
+fn get_default_follow_redirects() -> u8 {
+    1
+}
@@ -0,0 +1,181 @@
+#[cfg(unix)]
+pub mod syslog;
+
+#[cfg(not(unix))]
+use anyhow::anyhow;
+use anyhow::Context;
+use log4rs::append::console::{ConsoleAppender, Target};
+use log4rs::append::file::FileAppender;
+use log4rs::append::rolling_file::policy::compound::roll::delete::DeleteRoller;
+use log4rs::append::rolling_file::policy::compound::roll::fixed_window::FixedWindowRoller;
+use log4rs::append::rolling_file::policy::compound::roll::Roll;
+use log4rs::append::rolling_file::policy::compound::trigger::size::SizeTrigger;
+use log4rs::append::rolling_file::policy::compound::CompoundPolicy;
+use log4rs::append::rolling_file::RollingFileAppender;
+use log4rs::append::Append;
+use log4rs::config::{Appender, Config, Logger, Root};
+use log4rs::encode::json::JsonEncoder;
+use log4rs::encode::pattern::PatternEncoder;
+use log4rs::encode::Encode;
+use log4rs::filter::threshold::ThresholdFilter;
+use log::LevelFilter;
+
+use crate::config::logging::{LogFormat, LogTarget, LoggingConfig};
+use crate::config::size::parse_size;
+#[cfg(unix)]
+use crate::logging::syslog::SyslogAppender;
+use crate::types::OperationResult;
+
+const APPENDER_NAME: &str = "main";
+
+const TEXT_PATTERN: &str = "{d(%Y-%m-%d %H:%M:%S)} - {l} - [{M}] - {m}{n}";
+
+/// Syslog adds timestamp on its own
+#[cfg(unix)]
+const SYSLOG_TEXT_PATTERN: &str = "{l} - [{M}] - {m}";
+
+const HOST_CONTEXT_KEY: &str = "host";
+const URL_CONTEXT_KEY: &str = "url";
+
+fn get_logging_level_from_string(level: &str) -> LevelFilter {
+    return match level {
+        "debug" => LevelFilter::Debug,
+        "error" => LevelFilter::Error,
+        "warn" => LevelFilter::Warn,
+        "trace" => LevelFilter::Trace,
+        "off" => LevelFilter::Off,
+        _ => LevelFilter::Info
+    };
+}
+
+pub fn get_logging_config(logging_level: &str, logging_config: &LoggingConfig) -> OperationResult<Config> {
+    let level = get_logging_level_from_string(logging_level);
+
+    let appender = get_appender(logging_config)
+        .context(format!("unable to create log appender ({logging_config})"))?;
+
+    let config = Config::builder()
+        .appender(
+            Appender::builder()
+                .filter(Box::new(ThresholdFilter::new(level)))
+                .build(APPENDER_NAME, appender)
+        )
+        .logger(get_default_logger(level))
+        .logger(Logger::builder().build("hyper", LevelFilter::Info))
+        .logger(Logger::builder().build("http", LevelFilter::Info))
+        .build(
+            Root::builder()
+                .appender(APPENDER_NAME)
+                .build(level)
+        )?;
+
+    Ok(config)
+}
+
+/// Host and url of the following log records of the current thread. Written as `mdc` field
+/// of JSON format.
+pub fn set_log_context(host: &str, url: Option<&str>) {
+    log_mdc::insert(HOST_CONTEXT_KEY, host);
+
+    match url {
+        Some(url) => log_mdc::insert(URL_CONTEXT_KEY, url),
+        None => log_mdc::remove(URL_CONTEXT_KEY),
+    };
+}
+
+pub fn clear_log_context() {
+    log_mdc::remove(HOST_CONTEXT_KEY);
+    log_mdc::remove(URL_CONTEXT_KEY);
+}
+
+fn get_appender(logging_config: &LoggingConfig) -> OperationResult<Box<dyn Append>> {
+    let encoder = get_encoder(logging_config.format, TEXT_PATTERN);
+
+    let appender: Box<dyn Append> = match logging_config.target {
+        LogTarget::File => get_file_appender(logging_config, encoder)?,
+        LogTarget::Stdout => Box::new(ConsoleAppender::builder().encoder(encoder).target(Target::Stdout).build()),
+        LogTarget::Stderr => Box::new(ConsoleAppender::builder().encoder(encoder).target(Target::Stderr).build()),
+        #[cfg(unix)]
+        LogTarget::Syslog => Box::new(SyslogAppender::new(get_encoder(logging_config.format, SYSLOG_TEXT_PATTERN))?),
+        #[cfg(not(unix))]
+        LogTarget::Syslog => return Err(anyhow!("syslog log target is supported on unix only")),
+    };
+
+    Ok(appender)
+}
+
+/// Log file is rotated with `rotation` option: `wszl.log` -> `wszl.log.0` -> `wszl.log.1`..
+fn get_file_appender(logging_config: &LoggingConfig, encoder: Box<dyn Encode>) -> OperationResult<Box<dyn Append>> {
+    let file = &logging_config.file;
+
+    match &logging_config.rotation {
+        Some(rotation) => {
+            let max_size = parse_size(&rotation.max_size)?;
+
+            let roller: Box<dyn Roll> = match rotation.keep {
+                0 => Box::new(DeleteRoller::new()),
+                keep => Box::new(FixedWindowRoller::builder().build(&format!("{file}.{{}}"), keep)?),
+            };
+
+            let policy = CompoundPolicy::new(Box::new(SizeTrigger::new(max_size)), roller);
+
+            Ok(Box::new(RollingFileAppender::builder().encoder(encoder).build(file, Box::new(policy))?))
+        }
+        None => Ok(Box::new(FileAppender::builder().encoder(encoder).build(file)?)),
+    }
+}
+
+fn get_encoder(format: LogFormat, text_pattern: &str) -> Box<dyn Encode> {
+    match format {
+        LogFormat::Text => Box::new(PatternEncoder::new(text_pattern)),
+        LogFormat::Json => Box::new(JsonEncoder::new()),
+    }
+}
+
+fn get_default_logger(level: LevelFilter) -> Logger {
+    Logger::builder()
+        .build("default", level)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::env::temp_dir;
+    use std::fs;
+    use std::process;
+
+    use crate::config::logging::{LogFormat, LogRotationConfig, LogTarget, LoggingConfig};
+    use crate::logging::get_logging_config;
+
+    #[test]
+    fn console_and_rolling_file_configs_should_be_created() {
+        let console_config = LoggingConfig {
+            target: LogTarget::Stderr,
+            format: LogFormat::Json,
+            ..LoggingConfig::default()
+        };
+
+        assert!(get_logging_config("debug", &console_config).is_ok());
+
+        let file = temp_dir().join(format!("wszl-{}.log", process::id()));
+
+        let mut file_config = LoggingConfig {
+            file: file.display().to_string(),
+            rotation: Some(LogRotationConfig {
+                max_size: "10m".to_string(),
+                keep: 3,
+            }),
+            ..LoggingConfig::default()
+        };
+
+        assert!(get_logging_config("info", &file_config).is_ok());
+
+        file_config.rotation = Some(LogRotationConfig {
+            max_size: "ten".to_string(),
+            keep: 3,
+        });
+
+        assert!(get_logging_config("info", &file_config).is_err());
+
+        let _ = fs::remove_file(file);
+    }
+}
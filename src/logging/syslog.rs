@@ -0,0 +1,77 @@
+use std::os::unix::net::UnixDatagram;
+use std::process;
+
+use anyhow::Context;
+use log::{Level, Record};
+use log4rs::append::Append;
+use log4rs::encode::writer::simple::SimpleWriter;
+use log4rs::encode::Encode;
+
+use crate::types::OperationResult;
+
+const SYSLOG_SOCKET_PATH: &str = "/dev/log";
+
+const SYSLOG_APP_NAME: &str = "wszl";
+
+/// Facility `user`
+const SYSLOG_FACILITY: u8 = 1;
+
+/// Sends records to local syslog socket in RFC 3164 format: `<14>wszl[1234]: message`.
+/// Syslog daemon adds timestamp and hostname.
+#[derive(Debug)]
+pub struct SyslogAppender {
+    socket: UnixDatagram,
+    encoder: Box<dyn Encode>,
+}
+
+impl SyslogAppender {
+    pub fn new(encoder: Box<dyn Encode>) -> OperationResult<SyslogAppender> {
+        let socket = UnixDatagram::unbound()?;
+
+        socket.connect(SYSLOG_SOCKET_PATH)
+            .context(format!("unable to connect to syslog socket '{SYSLOG_SOCKET_PATH}'"))?;
+
+        Ok(SyslogAppender { socket, encoder })
+    }
+}
+
+impl Append for SyslogAppender {
+    fn append(&self, record: &Record) -> anyhow::Result<()> {
+        let mut writer = SimpleWriter(Vec::new());
+        self.encoder.encode(&mut writer, record)?;
+
+        let message = format_syslog_message(record.level(), process::id(), &String::from_utf8_lossy(&writer.0));
+
+        self.socket.send(message.as_bytes())?;
+
+        Ok(())
+    }
+
+    fn flush(&self) {}
+}
+
+fn format_syslog_message(level: Level, pid: u32, message: &str) -> String {
+    let severity = match level {
+        Level::Error => 3,
+        Level::Warn => 4,
+        Level::Info => 6,
+        Level::Debug | Level::Trace => 7,
+    };
+
+    let priority = SYSLOG_FACILITY * 8 + severity;
+
+    format!("<{priority}>{SYSLOG_APP_NAME}[{pid}]: {}", message.trim_end())
+}
+
+#[cfg(test)]
+mod tests {
+    use log::Level;
+
+    use crate::logging::syslog::format_syslog_message;
+
+    #[test]
+    fn syslog_message_should_have_priority_and_tag() {
+        assert_eq!(format_syslog_message(Level::Info, 42, "INFO - started\n"), "<14>wszl[42]: INFO - started");
+        assert_eq!(format_syslog_message(Level::Error, 42, "failed"), "<11>wszl[42]: failed");
+    }
+}
@@ -1,8 +1,11 @@
 use std::fs;
 
-use anyhow::Context;
+use anyhow::{anyhow, Context};
+use zabbix_api::host::model::ZabbixHostTag;
 
-use crate::source::{UrlSource, UrlSourceProvider};
+use crate::config::trigger::MAX_TRIGGER_PRIORITY;
+use crate::config::ws::HttpField;
+use crate::source::{InvalidUrl, UrlSource, UrlSourceOverrides, UrlSourceProvider, UrlSources};
 use crate::types::OperationResult;
 
 #[cfg(windows)]
@@ -10,6 +13,22 @@ const LINE_ENDING: &'static str = "\r\n";
 #[cfg(not(windows))]
 const LINE_ENDING: &'static str = "\n";
 
+const COMMENT_PREFIX: &str = "#";
+
+const ROW_SEPARATOR: char = '|';
+const OPTIONS_SEPARATOR: char = ';';
+const VALUES_SEPARATOR: char = ',';
+
+/// Escapes separator inside option values: `\;` and `\,`
+const ESCAPE_CHAR: char = '\\';
+
+const EXPECT_STATUS_CODE_OPTION: &str = "expect-status-code";
+const REQUIRED_STRING_OPTION: &str = "required-string";
+const REQUIRED_HEADERS_OPTION: &str = "required-headers";
+const UPDATE_INTERVAL_OPTION: &str = "update-interval";
+const PRIORITY_OPTION: &str = "priority";
+const TAGS_OPTION: &str = "tags";
+
 pub struct FileUrlSourceProvider {
     filename: String
 }
@@ -23,7 +42,7 @@ impl FileUrlSourceProvider {
 }
 
 impl UrlSourceProvider for FileUrlSourceProvider {
-    fn get_url_sources(&self) -> OperationResult<Vec<UrlSource>> {
+    fn get_url_sources(&self) -> OperationResult<UrlSources> {
         info!("extracting url sources from file '{}'..", self.filename);
 
         let content = fs::read_to_string(&self.filename)
@@ -31,38 +50,160 @@ impl UrlSourceProvider for FileUrlSourceProvider {
 
         let rows = content.split(LINE_ENDING).collect::<Vec<&str>>();
 
-        let mut results: Vec<UrlSource> = vec![];
+        let mut results = UrlSources::default();
 
-        for row in rows {
+        for (index, row) in rows.iter().enumerate() {
 
-            let row_parts = row.split("|").collect::<Vec<&str>>();
+            if row.trim().is_empty() || row.starts_with(COMMENT_PREFIX) {
+                continue;
+            }
 
-            if row_parts.len() == 2 && !row.starts_with("#") {
-                let hostname = row_parts.first().unwrap();
-                let url = row_parts.last().unwrap();
+            // Options are the rest of the row, regular expressions may contain '|'
+            let row_parts = row.splitn(3, ROW_SEPARATOR).collect::<Vec<&str>>();
+
+            if row_parts.len() >= 2 {
+                let hostname = row_parts[0];
+                let url = row_parts[1];
+
+                if !url.to_lowercase().starts_with("http") {
+                    warn!("invalid url '{url}' at line {} (skip)", index + 1);
+                    results.invalid_urls.push(InvalidUrl {
+                        host: hostname.to_string(),
+                        url: url.to_string(),
+                        reason: format!("line {}: url must start with http:// or https://", index + 1),
+                    });
+                    continue;
+                }
+
+                let overrides = match row_parts.get(2) {
+                    Some(options) => match parse_overrides(options) {
+                        Ok(overrides) => overrides,
+                        Err(e) => {
+                            warn!("invalid options at line {}: {} (skip)", index + 1, e);
+                            results.invalid_urls.push(InvalidUrl {
+                                host: hostname.to_string(),
+                                url: url.to_string(),
+                                reason: format!("line {}: {e}", index + 1),
+                            });
+                            continue;
+                        }
+                    },
+                    None => UrlSourceOverrides::default()
+                };
 
                 let url_source = UrlSource {
                     zabbix_host: hostname.to_string(),
+                    host_id: None,
                     url: url.to_string(),
+                    overrides,
                 };
 
                 debug!("add url source: {:?}", url_source);
 
-                results.push(url_source)
+                results.url_sources.push(url_source)
 
             } else {
-                debug!("row doesn't match pattern: '{row}' (skip)");
+                warn!("row doesn't match pattern at line {}: '{row}' (skip)", index + 1);
+                results.invalid_urls.push(InvalidUrl {
+                    host: "".to_string(),
+                    url: row.to_string(),
+                    reason: format!("line {}: row doesn't match pattern host|url[|options]", index + 1),
+                });
             }
         }
 
         Ok(results)
     }
+
+    fn get_source_type(&self) -> &str {
+        "file"
+    }
+}
+
+/// Parse per-url options: `expect-status-code=200,301;priority=5;tags=env:prod,team:web`.
+/// Required headers: `required-headers=Content-Type:application/json,X-Frame-Options`.
+/// Separators inside values are escaped with backslash: `required-string=a\;b`, `tags=team:web\,api`
+fn parse_overrides(options: &str) -> OperationResult<UrlSourceOverrides> {
+    let mut overrides = UrlSourceOverrides::default();
+
+    for option in split_escaped(options, OPTIONS_SEPARATOR).iter().filter(|o| !o.trim().is_empty()) {
+        let (key, value) = option.split_once('=')
+                                 .context(format!("option '{option}' doesn't match pattern key=value"))?;
+
+        let value = value.trim();
+
+        match key.trim() {
+            EXPECT_STATUS_CODE_OPTION => overrides.expect_status_code = Some(value.to_string()),
+            REQUIRED_STRING_OPTION => overrides.required_string = Some(value.to_string()),
+            REQUIRED_HEADERS_OPTION => {
+                overrides.required_headers = split_escaped(value, VALUES_SEPARATOR).iter()
+                    .filter(|h| !h.is_empty())
+                    .map(|h| {
+                        let (name, value) = h.split_once(':').unwrap_or((h.as_str(), ".*"));
+                        HttpField { name: name.to_string(), value: value.to_string() }
+                    })
+                    .collect()
+            }
+            UPDATE_INTERVAL_OPTION => overrides.update_interval = Some(value.to_string()),
+            PRIORITY_OPTION => {
+                let priority = value.parse::<u8>()
+                                    .context(format!("invalid priority value '{value}'"))?;
+
+                if priority > MAX_TRIGGER_PRIORITY {
+                    return Err(anyhow!("priority {priority} is out of range 0-{MAX_TRIGGER_PRIORITY}"));
+                }
+                overrides.trigger_priority = Some(priority)
+            }
+            TAGS_OPTION => {
+                overrides.tags = split_escaped(value, VALUES_SEPARATOR).iter()
+                    .filter(|t| !t.is_empty())
+                    .map(|t| {
+                        let (tag, value) = t.split_once(':').unwrap_or((t.as_str(), ""));
+                        ZabbixHostTag { tag: tag.to_string(), value: value.to_string() }
+                    })
+                    .collect()
+            }
+            _ => return Err(anyhow!("unknown option '{key}'"))
+        }
+    }
+
+    Ok(overrides)
+}
+
+/// Split by separator which isn't escaped, escaped separators are unescaped.
+/// Other escape sequences are kept as is, i.e. `\s` in regular expressions.
+fn split_escaped(value: &str, separator: char) -> Vec<String> {
+    let mut parts = vec![];
+    let mut part = String::new();
+
+    let mut chars = value.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if c == ESCAPE_CHAR && chars.peek() == Some(&separator) {
+            part.push(separator);
+            chars.next();
+
+        } else if c == separator {
+            parts.push(part);
+            part = String::new();
+
+        } else {
+            part.push(c);
+        }
+    }
+
+    parts.push(part);
+
+    parts
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::source::file::FileUrlSourceProvider;
-    use crate::source::UrlSourceProvider;
+    use zabbix_api::host::model::ZabbixHostTag;
+
+    use crate::config::ws::HttpField;
+    use crate::source::file::{parse_overrides, FileUrlSourceProvider};
+    use crate::source::{UrlSourceOverrides, UrlSourceProvider};
     use crate::tests::init_logging;
 
     #[test]
@@ -72,16 +213,16 @@ mod tests {
         let provider = FileUrlSourceProvider::new("test-data/urls.txt");
         match provider.get_url_sources() {
             Ok(results) => {
-                assert_eq!(2, results.len());
+                assert_eq!(4, results.url_sources.len());
 
                 assert!(
-                    results.iter().find(|us|
+                    results.url_sources.iter().find(|us|
                         us.zabbix_host == "websrv10-182" &&
                         us.url == "https://demo.company.com").is_some()
                 );
 
                 assert!(
-                    results.iter().find(|us|
+                    results.url_sources.iter().find(|us|
                         us.zabbix_host == "Websrv121" &&
                             us.url == "https://app12.stage.company.com").is_some()
                 );
@@ -89,4 +230,89 @@ mod tests {
             Err(e) => error!("unexpected error '{}': {}", e, e.root_cause())
         }
     }
+
+    #[test]
+    fn url_source_options_should_be_parsed() {
+        init_logging();
+
+        let provider = FileUrlSourceProvider::new("test-data/urls.txt");
+        let results = provider.get_url_sources().unwrap();
+
+        let url_source = results.url_sources.iter()
+            .find(|us| us.url == "https://api.company.com/health").unwrap();
+
+        assert_eq!(url_source.zabbix_host, "Websrv121");
+        assert_eq!(url_source.overrides, UrlSourceOverrides {
+            expect_status_code: Some("200,204".to_string()),
+            required_string: Some("\"status\":\\s*\"ok\"".to_string()),
+            update_interval: Some("1m".to_string()),
+            trigger_priority: Some(5),
+            tags: vec![
+                ZabbixHostTag { tag: "env".to_string(), value: "prod".to_string() },
+                ZabbixHostTag { tag: "critical".to_string(), value: "".to_string() },
+            ],
+            required_headers: vec![HttpField {
+                name: "Content-Type".to_string(),
+                value: "application/json".to_string(),
+            }],
+            ..Default::default()
+        });
+
+        let url_source = results.url_sources.iter()
+            .find(|us| us.url == "https://demo.company.com").unwrap();
+
+        assert_eq!(url_source.overrides, UrlSourceOverrides::default());
+    }
+
+    #[test]
+    fn rows_with_invalid_options_should_be_reported() {
+        init_logging();
+
+        let provider = FileUrlSourceProvider::new("test-data/urls.txt");
+        let results = provider.get_url_sources().unwrap();
+
+        assert_eq!(results.invalid_urls.len(), 2);
+
+        let invalid_url = &results.invalid_urls[0];
+        assert_eq!(invalid_url.host, "broken");
+        assert_eq!(invalid_url.url, "https://broken.company.com");
+        assert!(invalid_url.reason.starts_with("line 10: invalid priority value 'high'"), "{}", invalid_url.reason);
+
+        let invalid_url = &results.invalid_urls[1];
+        assert_eq!(invalid_url.url, "broken row without url");
+        assert_eq!(invalid_url.reason, "line 12: row doesn't match pattern host|url[|options]");
+    }
+
+    #[test]
+    fn options_with_row_and_escaped_separators_should_be_parsed() {
+        init_logging();
+
+        let provider = FileUrlSourceProvider::new("test-data/urls.txt");
+        let results = provider.get_url_sources().unwrap();
+
+        let url_source = results.url_sources.iter()
+            .find(|us| us.url == "https://app12.stage.company.com/status").unwrap();
+
+        assert_eq!(url_source.overrides.required_string, Some("ok|healthy".to_string()));
+        assert_eq!(url_source.overrides.tags, vec![
+            ZabbixHostTag { tag: "team".to_string(), value: "web,api".to_string() },
+        ]);
+
+        let overrides = parse_overrides("required-string=a\\;b;required-headers=Cache-Control:no-cache\\,no-store,Pragma").unwrap();
+
+        assert_eq!(overrides.required_string, Some("a;b".to_string()));
+        assert_eq!(overrides.required_headers, vec![
+            HttpField { name: "Cache-Control".to_string(), value: "no-cache,no-store".to_string() },
+            HttpField { name: "Pragma".to_string(), value: ".*".to_string() },
+        ]);
+    }
+
+    #[test]
+    fn invalid_options_should_be_rejected() {
+        assert!(parse_overrides("priority=high").is_err());
+        assert!(parse_overrides("priority=9").is_err());
+        assert_eq!(parse_overrides("priority=5").unwrap().trigger_priority, Some(5));
+        assert!(parse_overrides("unknown=1").is_err());
+        assert!(parse_overrides("priority").is_err());
+    }
 }
\ No newline at end of file
@@ -1,17 +1,71 @@
+use serde::Serialize;
+use zabbix_api::host::model::ZabbixHostTag;
+
+use crate::config::ws::{HttpField, WebScenarioStepConfig};
 use crate::types::OperationResult;
 
 pub mod zabbix;
 pub mod file;
+pub mod structured;
 
 pub trait UrlSourceProvider {
-    fn get_url_sources(&self) -> OperationResult<Vec<UrlSource>>;
+    fn get_url_sources(&self) -> OperationResult<UrlSources>;
+
+    /// Source type, i.e. `file`. Stored in tags of created objects
+    fn get_source_type(&self) -> &str;
+}
+
+impl<T: UrlSourceProvider + ?Sized> UrlSourceProvider for Box<T> {
+    fn get_url_sources(&self) -> OperationResult<UrlSources> {
+        (**self).get_url_sources()
+    }
+
+    fn get_source_type(&self) -> &str {
+        (**self).get_source_type()
+    }
 }
 
-#[derive(Debug)]
+/// Url sources and urls which were skipped by provider
+#[derive(Default, Debug)]
+pub struct UrlSources {
+    pub url_sources: Vec<UrlSource>,
+    pub invalid_urls: Vec<InvalidUrl>,
+}
+
+#[derive(Serialize, PartialEq, Clone, Debug)]
+#[serde(rename_all = "kebab-case")]
+pub struct InvalidUrl {
+    pub host: String,
+    pub url: String,
+    pub reason: String,
+}
+
+#[derive(Default, Debug)]
 pub struct UrlSource {
     pub zabbix_host: String,
-    pub url: String
+    /// Zabbix host id when it's already known to the source, i.e. Zabbix source
+    pub host_id: Option<String>,
+    pub url: String,
+    pub overrides: UrlSourceOverrides
 }
 
+/// Per-url values which replace `scenario` and `trigger` config values
+#[derive(PartialEq, Default, Clone, Debug)]
+pub struct UrlSourceOverrides {
+    /// Web scenario name template
+    pub name: Option<String>,
+    pub expect_status_code: Option<String>,
+    pub required_string: Option<String>,
 
+    /// Replace `scenario.required-headers`
+    pub required_headers: Vec<HttpField>,
+    pub update_interval: Option<String>,
+    pub trigger_priority: Option<u8>,
+    pub tags: Vec<ZabbixHostTag>,
 
+    /// Added to `scenario.headers`
+    pub headers: Vec<HttpField>,
+
+    /// Replace `scenario.steps`
+    pub steps: Vec<WebScenarioStepConfig>
+}
@@ -0,0 +1,277 @@
+use std::fmt::{Display, Formatter};
+use std::fs;
+
+use anyhow::{anyhow, Context};
+use serde::{Deserialize, Deserializer};
+use zabbix_api::host::model::ZabbixHostTag;
+
+use crate::config::trigger::MAX_TRIGGER_PRIORITY;
+use crate::config::ws::{HttpField, WebScenarioStepConfig};
+use crate::source::{UrlSource, UrlSourceOverrides, UrlSourceProvider, UrlSources};
+use crate::types::OperationResult;
+
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub enum StructuredFormat {
+    Yaml,
+    Json,
+}
+
+impl Display for StructuredFormat {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let value = match self {
+            StructuredFormat::Yaml => "yaml",
+            StructuredFormat::Json => "json",
+        };
+
+        write!(f, "{value}")
+    }
+}
+
+/// Hosts and urls from YAML or JSON document:
+///
+/// ```yaml
+/// hosts:
+///   - host: websrv10-182
+///     urls:
+///       - url: https://demo.company.com
+///         priority: 5
+/// ```
+pub struct StructuredUrlSourceProvider {
+    filename: String,
+    format: StructuredFormat,
+}
+
+impl StructuredUrlSourceProvider {
+    pub fn new(filename: &str, format: StructuredFormat) -> StructuredUrlSourceProvider {
+        StructuredUrlSourceProvider {
+            filename: filename.to_string(),
+            format,
+        }
+    }
+}
+
+impl UrlSourceProvider for StructuredUrlSourceProvider {
+    fn get_url_sources(&self) -> OperationResult<UrlSources> {
+        info!("extracting url sources from {} file '{}'..", self.format, self.filename);
+
+        let content = fs::read_to_string(&self.filename)
+            .context(format!("unable to read url source file '{}'", self.filename))?;
+
+        let document = parse_document(&content, self.format)
+            .context(format!("invalid url source file '{}'", self.filename))?;
+
+        // Invalid documents are rejected as a whole, so there are no invalid urls to report
+        let mut results = UrlSources::default();
+
+        for host in document.hosts {
+            for url in host.urls {
+                let url_source = UrlSource {
+                    zabbix_host: host.host.to_string(),
+                    host_id: None,
+                    url: url.url,
+                    overrides: UrlSourceOverrides {
+                        name: url.name,
+                        expect_status_code: url.expect_status_code,
+                        required_string: url.required_string,
+                        required_headers: url.required_headers,
+                        update_interval: url.update_interval,
+                        trigger_priority: url.priority,
+                        tags: url.tags,
+                        headers: url.headers,
+                        steps: url.steps,
+                    },
+                };
+
+                debug!("add url source: {:?}", url_source);
+
+                results.url_sources.push(url_source)
+            }
+        }
+
+        Ok(results)
+    }
+
+    fn get_source_type(&self) -> &str {
+        match self.format {
+            StructuredFormat::Yaml => "yaml",
+            StructuredFormat::Json => "json",
+        }
+    }
+}
+
+/// Error message contains position of the invalid value, i.e. `hosts[0].urls[1]: unknown field
+/// `expected`, expected one of ... at line 5 column 9`
+fn parse_document(content: &str, format: StructuredFormat) -> OperationResult<UrlSourceDocument> {
+    match format {
+        StructuredFormat::Yaml => serde_yaml::from_str(content).map_err(|e| anyhow!("{e}")),
+        StructuredFormat::Json => serde_json::from_str(content).map_err(|e| anyhow!("{e}")),
+    }
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+struct UrlSourceDocument {
+    hosts: Vec<HostDocument>,
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+struct HostDocument {
+    #[serde(deserialize_with = "deserialize_not_empty")]
+    host: String,
+
+    urls: Vec<UrlDocument>,
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+struct UrlDocument {
+    #[serde(deserialize_with = "deserialize_url")]
+    url: String,
+
+    name: Option<String>,
+
+    expect_status_code: Option<String>,
+
+    required_string: Option<String>,
+
+    #[serde(default)]
+    required_headers: Vec<HttpField>,
+
+    update_interval: Option<String>,
+
+    #[serde(default, deserialize_with = "deserialize_priority")]
+    priority: Option<u8>,
+
+    #[serde(default)]
+    tags: Vec<ZabbixHostTag>,
+
+    #[serde(default)]
+    headers: Vec<HttpField>,
+
+    #[serde(default, deserialize_with = "deserialize_steps")]
+    steps: Vec<WebScenarioStepConfig>,
+}
+
+fn deserialize_not_empty<'de, D: Deserializer<'de>>(deserializer: D) -> Result<String, D::Error> {
+    let value = String::deserialize(deserializer)?;
+
+    if value.trim().is_empty() {
+        return Err(serde::de::Error::custom("value must not be empty"));
+    }
+
+    Ok(value)
+}
+
+fn deserialize_url<'de, D: Deserializer<'de>>(deserializer: D) -> Result<String, D::Error> {
+    let value = String::deserialize(deserializer)?;
+
+    if !value.starts_with("http://") && !value.starts_with("https://") {
+        return Err(serde::de::Error::custom(format!(
+            "url '{value}' must start with http:// or https://"
+        )));
+    }
+
+    Ok(value)
+}
+
+fn deserialize_priority<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Option<u8>, D::Error> {
+    let value = u8::deserialize(deserializer)?;
+
+    if value > MAX_TRIGGER_PRIORITY {
+        return Err(serde::de::Error::custom(format!(
+            "priority {value} is out of range 0-{MAX_TRIGGER_PRIORITY}"
+        )));
+    }
+
+    Ok(Some(value))
+}
+
+fn deserialize_steps<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Vec<WebScenarioStepConfig>, D::Error> {
+    let steps = Vec::<WebScenarioStepConfig>::deserialize(deserializer)?;
+
+    for step in &steps {
+        step.validate().map_err(serde::de::Error::custom)?;
+    }
+
+    Ok(steps)
+}
+
+#[cfg(test)]
+mod tests {
+    use zabbix_api::host::model::ZabbixHostTag;
+
+    use crate::config::ws::{HttpField, StepMethod};
+    use crate::source::structured::{parse_document, StructuredFormat, StructuredUrlSourceProvider};
+    use crate::source::{UrlSourceOverrides, UrlSourceProvider};
+
+    #[test]
+    fn url_sources_should_be_loaded_from_yaml_and_json() {
+        for (filename, format) in [
+            ("test-data/urls.yml", StructuredFormat::Yaml),
+            ("test-data/urls.json", StructuredFormat::Json),
+        ] {
+            let provider = StructuredUrlSourceProvider::new(filename, format);
+            let results = provider.get_url_sources().unwrap().url_sources;
+
+            assert_eq!(3, results.len());
+
+            let url_source = results.iter()
+                .find(|us| us.url == "https://api.company.com/health").unwrap();
+
+            assert_eq!(url_source.zabbix_host, "Websrv121");
+            assert_eq!(url_source.overrides.steps.len(), 2);
+            assert_eq!(url_source.overrides.steps[0].method, StepMethod::Post);
+            assert_eq!(url_source.overrides.steps[1].required_string, "ok");
+            assert_eq!(url_source.overrides, UrlSourceOverrides {
+                name: Some("API health '${URL}'".to_string()),
+                expect_status_code: Some("200,204".to_string()),
+                trigger_priority: Some(5),
+                tags: vec![ZabbixHostTag { tag: "env".to_string(), value: "prod".to_string() }],
+                headers: vec![HttpField {
+                    name: "Accept".to_string(),
+                    value: "application/json".to_string(),
+                }],
+                steps: url_source.overrides.steps.clone(),
+                ..Default::default()
+            });
+
+            let url_source = results.iter()
+                .find(|us| us.url == "https://demo.company.com").unwrap();
+
+            assert_eq!(url_source.zabbix_host, "websrv10-182");
+            assert_eq!(url_source.overrides, UrlSourceOverrides::default());
+        }
+    }
+
+    #[test]
+    fn validation_error_should_point_to_line() {
+        let content = "hosts:\n  - host: srv1\n    urls:\n      - url: https://a.com\n        expected: 200\n";
+
+        let error = parse_document(content, StructuredFormat::Yaml).unwrap_err().to_string();
+        assert!(error.contains("unknown field `expected`"), "{error}");
+        assert!(error.contains("line 5"), "{error}");
+
+        let content = "hosts:\n  - host: srv1\n    urls:\n      - url: ftp://a.com\n";
+
+        let error = parse_document(content, StructuredFormat::Yaml).unwrap_err().to_string();
+        assert!(error.contains("must start with http"), "{error}");
+        assert!(error.contains("line 4"), "{error}");
+
+        let content = "{\"hosts\": [{\"host\": \"srv1\",\n \"urls\": [{\"url\": \"https://a.com\",\n \"priority\": 7}]}]}";
+
+        let error = parse_document(content, StructuredFormat::Json).unwrap_err().to_string();
+        assert!(error.contains("out of range"), "{error}");
+        assert!(error.contains("line 3"), "{error}");
+
+        let content = "hosts:\n  - host: srv1\n    urls:\n      - url: https://a.com\n        steps:\n          \
+            - name: Index\n            url: https://a.com\n            method: head\n            required-string: ok\n";
+
+        let error = parse_document(content, StructuredFormat::Yaml).unwrap_err().to_string();
+        assert!(error.contains("required-string isn't allowed for head method"), "{error}");
+    }
+}
@@ -5,12 +5,13 @@ use zabbix_api::client::client::ZabbixApiClient;
 use zabbix_api::item::get::GetItemsRequestByKey;
 
 use crate::config::ZabbixConfig;
-use crate::source::{UrlSource, UrlSourceProvider};
+use crate::source::{InvalidUrl, UrlSource, UrlSourceProvider, UrlSources};
 use crate::types::OperationResult;
 
 pub struct ZabbixUrlSourceProvider<T: ZabbixApiClient> {
     pub zabbix_config: ZabbixConfig,
     pub zabbix_client: T,
+    pub session: String,
     pub item_key_search_mask: String,
 }
 
@@ -18,30 +19,26 @@ impl<T: ZabbixApiClient> ZabbixUrlSourceProvider<T> {
     pub fn new(
         zabbix_config: &ZabbixConfig,
         zabbix_service: T,
+        session: &str,
         item_key_search_mask: &str,
     ) -> ZabbixUrlSourceProvider<T> {
         ZabbixUrlSourceProvider {
             zabbix_config: zabbix_config.clone(),
             zabbix_client: zabbix_service,
+            session: session.to_string(),
             item_key_search_mask: item_key_search_mask.to_string(),
         }
     }
 }
 
 impl<T: ZabbixApiClient> UrlSourceProvider for ZabbixUrlSourceProvider<T> {
-    fn get_url_sources(&self) -> OperationResult<Vec<UrlSource>> {
+    fn get_url_sources(&self) -> OperationResult<UrlSources> {
         info!(
             "getting url sources from zabbix server '{}'..",
             &self.zabbix_config.api.endpoint
         );
 
-        let auth_token = &self
-            .zabbix_client
-            .get_auth_session(
-                &self.zabbix_config.api.username,
-                &self.zabbix_config.api.password,
-            )
-            .context("zabbix auth error")?;
+        let auth_token = &self.session;
 
         let request = GetItemsRequestByKey::new(&self.item_key_search_mask);
 
@@ -80,7 +77,7 @@ impl<T: ZabbixApiClient> UrlSourceProvider for ZabbixUrlSourceProvider<T> {
 
         debug!("hosts received: {:?}", hosts);
 
-        let mut results: Vec<UrlSource> = vec![];
+        let mut results = UrlSources::default();
 
         let pattern_start = "^".to_string() + &self.item_key_search_mask;
         let pattern = pattern_start + "\\[(.*)\\]$";
@@ -102,19 +99,36 @@ impl<T: ZabbixApiClient> UrlSourceProvider for ZabbixUrlSourceProvider<T> {
                     if let Some(host) = hosts.iter().find(|host| host.host_id == item.host_id) {
                         let url_source = UrlSource {
                             zabbix_host: host.host.to_string(),
+                            host_id: Some(host.host_id.to_string()),
                             url,
+                            ..Default::default()
                         };
 
                         debug!("add url source: {:?}", url_source);
 
-                        results.push(url_source)
+                        results.url_sources.push(url_source)
                     }
                 } else {
-                    warn!("invalid url found '{}' for item '{}'", url, item.name)
+                    warn!("invalid url found '{}' for item '{}'", url, item.name);
+
+                    let host = hosts.iter()
+                        .find(|host| host.host_id == item.host_id)
+                        .map(|host| host.host.to_string())
+                        .unwrap_or_default();
+
+                    results.invalid_urls.push(InvalidUrl {
+                        host,
+                        url,
+                        reason: format!("item '{}': url must start with http:// or https://", item.key_),
+                    });
                 }
             }
         }
 
         Ok(results)
     }
+
+    fn get_source_type(&self) -> &str {
+        "zabbix"
+    }
 }
@@ -4,6 +4,9 @@ pub const HOST_TEMPLATE_VAR: &str = "HOST";
 pub const URL_TEMPLATE_VAR: &str = "URL";
 pub const URL_WITHOUT_PROTOCOL_TEMPLATE_VAR: &str = "URL_WITHOUT_PROTOCOL";
 
+/// Web scenario name of the url, available in item and trigger templates
+pub const SCENARIO_NAME_TEMPLATE_VAR: &str = "SCENARIO_NAME";
+
 pub fn process_template_string(input: &str, template_vars: &HashMap<String, String>) -> String {
     let mut result: String = input.to_string();
 
@@ -15,6 +18,8 @@ pub fn process_template_string(input: &str, template_vars: &HashMap<String, Stri
     result.to_string()
 }
 
+/// Variables of item, trigger and web scenario templates. `SCENARIO_NAME_TEMPLATE_VAR` is added
+/// once web scenario name is rendered, so it's available everywhere except `scenario.name-template`
 pub fn get_template_vars(host: &str, url: &str) -> HashMap<String, String> {
     let url_without_protocol = url.replace("https://", "")
                                          .replace("http://", "");
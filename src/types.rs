@@ -1,6 +1,136 @@
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+
 use anyhow;
 
 pub type OperationResult<R> = anyhow::Result<R>;
 pub type OptionalResult<R> = anyhow::Result<Option<R>>;
 
-pub type EmptyResult = anyhow::Result<()>;
\ No newline at end of file
+pub type EmptyResult = anyhow::Result<()>;
+
+/// Result of module boundaries: config loading, Zabbix auth, url source and generation
+pub type AppResult<R> = Result<R, AppError>;
+
+/// Failure kinds which are told apart by exit code, see `cli` module
+#[derive(Debug)]
+pub enum AppError {
+    /// Config file is missing or invalid
+    Config(anyhow::Error),
+
+    /// Zabbix API login failed
+    Auth(anyhow::Error),
+
+    /// Url source can't be read, i.e. missing file or invalid YAML
+    Source(anyhow::Error),
+
+    /// Zabbix API returned error for the request, i.e. invalid trigger expression
+    Api(anyhow::Error),
+
+    /// Run is finished, but some urls failed. Urls with unresolved hosts are counted with `--strict`
+    PartialFailure { failed: usize, unresolved: usize },
+
+    /// The rest of errors, i.e. Zabbix API is unavailable
+    Other(anyhow::Error),
+}
+
+impl AppError {
+    /// Error or any of its causes is the Zabbix API error response
+    pub fn is_api_error(error: &anyhow::Error) -> bool {
+        error.chain().any(|cause| matches!(cause.downcast_ref::<AppError>(), Some(AppError::Api(_))))
+    }
+
+    /// Zabbix API is unreachable: connection, DNS or TLS error
+    pub fn is_transport_error(error: &anyhow::Error) -> bool {
+        error.chain().any(|cause| cause.is::<reqwest::Error>())
+    }
+
+    /// Transport errors are `Other`, the rest is wrapped with `kind`, i.e. `AppError::Auth`.
+    /// Unavailable Zabbix API isn't reported as auth or url source failure
+    pub fn unless_transport(error: anyhow::Error, kind: fn(anyhow::Error) -> AppError) -> AppError {
+        match AppError::is_transport_error(&error) {
+            true => AppError::Other(error),
+            false => kind(error),
+        }
+    }
+
+    pub fn root_cause(&self) -> String {
+        match self {
+            AppError::Config(e) | AppError::Auth(e) | AppError::Source(e) | AppError::Api(e) | AppError::Other(e) => {
+                e.root_cause().to_string()
+            }
+            AppError::PartialFailure { .. } => self.to_string(),
+        }
+    }
+}
+
+/// Zabbix API errors keep the original error type, see `AppError::is_api_error`
+impl From<anyhow::Error> for AppError {
+    fn from(error: anyhow::Error) -> Self {
+        match AppError::is_api_error(&error) {
+            true => AppError::Api(error),
+            false => AppError::Other(error),
+        }
+    }
+}
+
+impl Display for AppError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AppError::Config(e) => write!(f, "config load error: {e}"),
+            AppError::Auth(e) => write!(f, "zabbix auth error: {e}"),
+            AppError::Source(e) => write!(f, "url source error: {e}"),
+            AppError::Api(e) | AppError::Other(e) => write!(f, "{e}"),
+            AppError::PartialFailure { failed, unresolved } => {
+                write!(f, "generation failed for {failed} urls, unresolved hosts for {unresolved} urls")
+            }
+        }
+    }
+}
+
+impl Error for AppError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            AppError::Config(e) | AppError::Auth(e) | AppError::Source(e) | AppError::Api(e) | AppError::Other(e) => {
+                e.source()
+            }
+            AppError::PartialFailure { .. } => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use anyhow::{anyhow, Context};
+
+    use crate::types::{AppError, OperationResult};
+
+    fn call_api() -> OperationResult<()> {
+        Err(AppError::Api(anyhow!("zabbix api method 'trigger.create' error: invalid expression")).into())
+    }
+
+    #[test]
+    fn api_errors_should_be_found_through_context() {
+        let error = call_api().context("unable to create trigger 'a'").unwrap_err();
+
+        assert!(AppError::is_api_error(&error));
+        assert!(!AppError::is_api_error(&anyhow!("connection refused")));
+
+        let error = AppError::from(error);
+
+        assert!(matches!(error, AppError::Api(_)));
+        assert_eq!(error.to_string(), "unable to create trigger 'a'");
+        assert_eq!(error.root_cause(), "zabbix api method 'trigger.create' error: invalid expression");
+    }
+
+    #[test]
+    fn transport_errors_should_not_be_reported_as_auth_errors() {
+        let transport_error = reqwest::blocking::get("invalid url").unwrap_err();
+        let error = anyhow::Error::from(transport_error).context("unable to get auth session");
+
+        assert!(matches!(AppError::unless_transport(error, AppError::Auth), AppError::Other(_)));
+
+        let error = anyhow!("Login name or password is incorrect.").context("unable to get auth session");
+
+        assert!(matches!(AppError::unless_transport(error, AppError::Auth), AppError::Auth(_)));
+    }
+}
@@ -0,0 +1,103 @@
+use std::fmt::Debug;
+use std::sync::OnceLock;
+
+use anyhow::anyhow;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use serde_json::{Map, Value};
+use zabbix_api::client::client::ZabbixApiClient;
+
+use crate::types::{AppError, EmptyResult, OperationResult};
+use crate::zabbix::rate::RateLimiter;
+
+/// Zabbix API: https://www.zabbix.com/documentation/6.0/en/manual/api/reference/httptest/get
+pub const TAG_OPERATOR_EQUALS: u8 = 1;
+pub const TAG_OPERATOR_EXISTS: u8 = 4;
+
+static RATE_LIMITER: OnceLock<RateLimiter> = OnceLock::new();
+
+/// Limit requests per second to Zabbix API for the whole run. Can be set once
+pub fn set_rate_limit(requests_per_second: u32) {
+    if RATE_LIMITER.set(RateLimiter::new(requests_per_second)).is_err() {
+        warn!("zabbix api rate limit is already set");
+    }
+}
+
+/// Block until the next request is allowed by rate limit
+pub fn wait_for_rate_limit() {
+    if let Some(rate_limiter) = RATE_LIMITER.get() {
+        rate_limiter.acquire();
+    }
+}
+
+/// Call Zabbix API method which isn't covered by `ZabbixApiClient`
+pub fn call_api_method<P: Serialize, R: DeserializeOwned>(
+    zabbix_client: &impl ZabbixApiClient,
+    session: &str,
+    method: &str,
+    params: &P,
+) -> OperationResult<R> {
+    wait_for_rate_limit();
+
+    let response = zabbix_client.raw_api_call::<P, R>(session, method, params)?;
+
+    match response.result {
+        Some(result) => Ok(result),
+        None => Err(AppError::Api(anyhow!("zabbix api method '{method}' error: {:?}", response.error)).into()),
+    }
+}
+
+/// Create several objects with a single request, i.e. `item.create`.
+/// Returns ids of created objects from `ids_field` of the response
+pub fn create_objects<R: Serialize + Debug>(
+    zabbix_client: &impl ZabbixApiClient,
+    session: &str,
+    method: &str,
+    ids_field: &str,
+    requests: &[R],
+) -> OperationResult<Vec<String>> {
+    debug!("'{method}' requests: {:?}", requests);
+
+    let response: Map<String, Value> = call_api_method(zabbix_client, session, method, &requests)?;
+
+    let ids = response
+        .get(ids_field)
+        .and_then(|ids| ids.as_array())
+        .map(|ids| ids.iter().filter_map(|id| id.as_str().map(|id| id.to_string())).collect())
+        .unwrap_or_default();
+
+    Ok(ids)
+}
+
+/// Update fields of existing object, i.e. `trigger.update`
+pub fn update_object(
+    zabbix_client: &impl ZabbixApiClient,
+    session: &str,
+    method: &str,
+    id_field: &str,
+    id: &str,
+    fields: &Map<String, Value>,
+) -> EmptyResult {
+    let mut params = fields.clone();
+    params.insert(id_field.to_string(), Value::String(id.to_string()));
+
+    debug!("'{method}' params: {:?}", params);
+
+    call_api_method::<Map<String, Value>, Value>(zabbix_client, session, method, &params)?;
+
+    Ok(())
+}
+
+/// Delete objects by ids, i.e. `httptest.delete`
+pub fn delete_objects(
+    zabbix_client: &impl ZabbixApiClient,
+    session: &str,
+    method: &str,
+    ids: &[String],
+) -> EmptyResult {
+    debug!("'{method}' ids: {:?}", ids);
+
+    call_api_method::<&[String], Value>(zabbix_client, session, method, &ids)?;
+
+    Ok(())
+}
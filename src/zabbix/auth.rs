@@ -0,0 +1,101 @@
+use anyhow::Context;
+use serde_json::Value;
+use zabbix_api::client::client::ZabbixApiClient;
+
+use crate::config::ZabbixApiConfig;
+use crate::types::{AppError, AppResult, EmptyResult};
+use crate::zabbix::api::call_api_method;
+
+/// Zabbix API error messages about expired or terminated session
+const SESSION_EXPIRED_MESSAGES: [&str; 2] = ["Session terminated", "Not authorized"];
+
+/// Returns session for Zabbix API calls. API token is used as is (Zabbix 5.4+),
+/// otherwise new session is created with username and password.
+pub fn login(zabbix_client: &impl ZabbixApiClient, api_config: &ZabbixApiConfig) -> AppResult<String> {
+    if !api_config.token.is_empty() {
+        info!("using zabbix api token");
+        return Ok(api_config.token.to_string());
+    }
+
+    info!("login to zabbix api as '{}'..", api_config.username);
+
+    let session = zabbix_client
+        .get_auth_session(&api_config.username, &api_config.password)
+        .context("unable to get auth session")
+        .map_err(|e| AppError::unless_transport(e, AppError::Auth))?;
+
+    Ok(session)
+}
+
+/// Close session created by `login`. Does nothing for API token.
+pub fn logout(
+    zabbix_client: &impl ZabbixApiClient,
+    api_config: &ZabbixApiConfig,
+    session: &str,
+) -> EmptyResult {
+    if !api_config.token.is_empty() {
+        return Ok(());
+    }
+
+    info!("logout from zabbix api..");
+
+    call_api_method::<Vec<String>, Value>(zabbix_client, session, "user.logout", &vec![])
+        .context("zabbix logout error")?;
+
+    Ok(())
+}
+
+/// Error message means session has expired, i.e. "Session terminated, re-login, please."
+pub fn is_session_expired(error_message: &str) -> bool {
+    SESSION_EXPIRED_MESSAGES.iter().any(|message| error_message.contains(message))
+}
+
+/// Session kept between runs, i.e. in daemon mode. Login happens on the first `get`
+/// and after `expire`.
+#[derive(Default)]
+pub struct ZabbixSession {
+    session: Option<String>,
+}
+
+impl ZabbixSession {
+    pub fn get(
+        &mut self,
+        zabbix_client: &impl ZabbixApiClient,
+        api_config: &ZabbixApiConfig,
+    ) -> AppResult<String> {
+        if let Some(session) = &self.session {
+            return Ok(session.to_string());
+        }
+
+        let session = login(zabbix_client, api_config)?;
+        self.session = Some(session.to_string());
+
+        Ok(session)
+    }
+
+    /// Expired session can't be closed, it's just forgotten
+    pub fn expire(&mut self) {
+        self.session = None;
+    }
+
+    pub fn close(&mut self, zabbix_client: &impl ZabbixApiClient, api_config: &ZabbixApiConfig) {
+        if let Some(session) = self.session.take() {
+            if let Err(e) = logout(zabbix_client, api_config, &session) {
+                warn!("{}", e);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::zabbix::auth::is_session_expired;
+
+    #[test]
+    fn expired_session_errors_should_be_detected() {
+        assert!(is_session_expired("zabbix api method 'item.get' error: Some(ZabbixApiErrorData { code: -32602, \
+            message: \"Invalid params.\", data: \"Session terminated, re-login, please.\" })"));
+        assert!(is_session_expired("Not authorized."));
+        assert!(!is_session_expired("Invalid params."));
+    }
+}
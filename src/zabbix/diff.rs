@@ -0,0 +1,155 @@
+use serde::Serialize;
+use serde_json::{Map, Value};
+
+use crate::types::OperationResult;
+
+/// Zabbix API returns these arrays in database order, not in the order they were sent
+const UNORDERED_FIELDS: [&str; 2] = ["tags", "dependencies"];
+
+/// Compare desired object with the one returned by Zabbix API.
+///
+/// Returns desired values of top-level fields which differ. Only fields present in
+/// the desired object are compared, nested objects are compared by the same rule.
+/// Zabbix API returns numbers as strings, so numbers are compared by their string form.
+/// Arrays are compared by position, except `UNORDERED_FIELDS`, i.e. `steps` order matters.
+pub fn get_changed_fields(
+    desired: &impl Serialize,
+    existing: &impl Serialize,
+) -> OperationResult<Map<String, Value>> {
+    let desired = serde_json::to_value(desired)?;
+    let existing = serde_json::to_value(existing)?;
+
+    let mut results = Map::new();
+
+    if let Value::Object(desired_fields) = desired {
+        for (key, desired_value) in desired_fields {
+            let matches = match existing.get(&key) {
+                Some(existing_value) => is_value_matches(&key, &desired_value, existing_value),
+                None => false,
+            };
+
+            if !matches {
+                results.insert(key, desired_value);
+            }
+        }
+    }
+
+    Ok(results)
+}
+
+pub fn get_field_names(fields: &Map<String, Value>) -> String {
+    fields.keys().cloned().collect::<Vec<String>>().join(", ")
+}
+
+/// `key` is the field name of compared values
+fn is_value_matches(key: &str, desired: &Value, existing: &Value) -> bool {
+    match (desired, existing) {
+        (Value::Object(desired_fields), Value::Object(_)) => {
+            desired_fields.iter().all(|(key, value)| match existing.get(key) {
+                Some(existing_value) => is_value_matches(key, value, existing_value),
+                None => false,
+            })
+        }
+        (Value::Array(desired_values), Value::Array(existing_values)) => {
+            if desired_values.len() != existing_values.len() {
+                return false;
+            }
+
+            if UNORDERED_FIELDS.contains(&key) {
+                is_unordered_array_matches(key, desired_values, existing_values)
+            } else {
+                desired_values
+                    .iter()
+                    .zip(existing_values)
+                    .all(|(desired_value, existing_value)| {
+                        is_value_matches(key, desired_value, existing_value)
+                    })
+            }
+        }
+        (Value::Number(number), Value::String(value)) => number.to_string() == *value,
+        _ => desired == existing,
+    }
+}
+
+/// Each desired value matches its own existing value, duplicates are counted
+fn is_unordered_array_matches(key: &str, desired_values: &[Value], existing_values: &[Value]) -> bool {
+    let mut matched = vec![false; existing_values.len()];
+
+    desired_values.iter().all(|desired_value| {
+        let index = existing_values.iter().enumerate().position(|(index, existing_value)| {
+            !matched[index] && is_value_matches(key, desired_value, existing_value)
+        });
+
+        match index {
+            Some(index) => {
+                matched[index] = true;
+                true
+            }
+            None => false,
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use crate::zabbix::diff::get_changed_fields;
+
+    #[test]
+    fn same_objects_should_have_no_changes() {
+        let desired = json!({"name": "demo", "priority": 4, "tags": [{"tag": "a", "value": "b"}]});
+        let existing = json!({"triggerid": "123", "name": "demo", "priority": "4",
+                              "tags": [{"tag": "a", "value": "b"}]});
+
+        let changes = get_changed_fields(&desired, &existing).unwrap();
+
+        assert!(changes.is_empty());
+    }
+
+    #[test]
+    fn changed_fields_should_be_returned_with_desired_values() {
+        let desired = json!({"name": "demo", "priority": 4, "url": "https://zabbix.com"});
+        let existing = json!({"name": "demo", "priority": "2"});
+
+        let changes = get_changed_fields(&desired, &existing).unwrap();
+
+        assert_eq!(changes.len(), 2);
+        assert_eq!(changes.get("priority").unwrap(), &json!(4));
+        assert_eq!(changes.get("url").unwrap(), &json!("https://zabbix.com"));
+    }
+
+    #[test]
+    fn nested_objects_should_be_compared_by_desired_fields() {
+        let desired = json!({"steps": [{"no": 1, "url": "https://zabbix.com"}]});
+        let existing = json!({"steps": [{"httpstepid": "5", "no": "1", "url": "https://zabbix.com"}]});
+
+        assert!(get_changed_fields(&desired, &existing).unwrap().is_empty());
+
+        let existing = json!({"steps": [{"no": "1", "url": "https://zabbix.com"},
+                                        {"no": "2", "url": "https://zabbix.com/api"}]});
+
+        assert!(get_changed_fields(&desired, &existing).unwrap().contains_key("steps"));
+    }
+
+    #[test]
+    fn tags_and_dependencies_should_be_compared_regardless_of_order() {
+        let desired = json!({"tags": [{"tag": "a", "value": "1"}, {"tag": "b", "value": "2"}],
+                             "dependencies": [{"triggerid": "10"}, {"triggerid": "20"}]});
+        let existing = json!({"tags": [{"tag": "b", "value": "2"}, {"tag": "a", "value": "1"}],
+                              "dependencies": [{"triggerid": "20", "description": "b"},
+                                               {"triggerid": "10", "description": "a"}]});
+
+        assert!(get_changed_fields(&desired, &existing).unwrap().is_empty());
+
+        let desired = json!({"tags": [{"tag": "a", "value": "1"}, {"tag": "a", "value": "1"}]});
+        let existing = json!({"tags": [{"tag": "a", "value": "1"}, {"tag": "b", "value": "2"}]});
+
+        assert!(get_changed_fields(&desired, &existing).unwrap().contains_key("tags"));
+
+        let desired = json!({"steps": [{"no": 1, "name": "a"}, {"no": 2, "name": "b"}]});
+        let existing = json!({"steps": [{"no": "2", "name": "b"}, {"no": "1", "name": "a"}]});
+
+        assert!(get_changed_fields(&desired, &existing).unwrap().contains_key("steps"));
+    }
+}
@@ -1,4 +1,8 @@
-use crate::types::OptionalResult;
+use std::collections::HashMap;
+use std::sync::{Mutex, MutexGuard};
+
+use crate::types::{OperationResult, OptionalResult};
+use crate::zabbix::api::wait_for_rate_limit;
 use serde_derive::Serialize;
 use zabbix_api::client::client::ZabbixApiClient;
 use zabbix_api::host::get::GetHostsRequest;
@@ -15,6 +19,8 @@ pub fn find_zabbix_host_id(
         },
     };
 
+    wait_for_rate_limit();
+
     let hosts_found = zabbix_client.get_hosts(&session, &request)?;
 
     match hosts_found.first() {
@@ -32,7 +38,136 @@ pub fn find_zabbix_host_id(
     }
 }
 
+/// Find ids of several hosts with a single request. Returns map hostname -> host id,
+/// hosts which weren't found are absent.
+pub fn find_zabbix_host_ids(
+    zabbix_client: &impl ZabbixApiClient,
+    session: &str,
+    hostnames: &[String],
+) -> OperationResult<HashMap<String, String>> {
+    info!("find zabbix host ids for {} hosts..", hostnames.len());
+
+    if hostnames.is_empty() {
+        return Ok(HashMap::new());
+    }
+
+    let request = GetHostsRequest {
+        filter: HostFilter {
+            host: hostnames.to_vec(),
+        },
+    };
+
+    wait_for_rate_limit();
+
+    let hosts_found = zabbix_client.get_hosts(session, &request)?;
+
+    debug!("zabbix hosts found: {}", hosts_found.len());
+
+    Ok(hosts_found
+        .into_iter()
+        .map(|host| (host.host, host.host_id))
+        .collect())
+}
+
+/// Host ids resolved during the run. Each host is requested from Zabbix at most once,
+/// hosts which weren't found are cached as well.
+#[derive(Default)]
+pub struct HostIdCache {
+    host_ids: Mutex<HashMap<String, Option<String>>>,
+}
+
+impl HostIdCache {
+    /// Add host id which is already known, i.e. from Zabbix url source
+    pub fn insert(&self, hostname: &str, host_id: &str) {
+        self.lock_host_ids().insert(hostname.to_string(), Some(host_id.to_string()));
+    }
+
+    pub fn find_host_id(
+        &self,
+        zabbix_client: &impl ZabbixApiClient,
+        session: &str,
+        hostname: &str,
+    ) -> OptionalResult<String> {
+        if let Some(host_id) = self.lock_host_ids().get(hostname) {
+            return Ok(host_id.clone());
+        }
+
+        let host_id = find_zabbix_host_id(zabbix_client, session, hostname)?;
+
+        self.lock_host_ids().insert(hostname.to_string(), host_id.clone());
+
+        Ok(host_id)
+    }
+
+    /// Unknown hosts are requested with a single request. Returns map hostname -> host id,
+    /// hosts which weren't found are absent.
+    pub fn find_host_ids(
+        &self,
+        zabbix_client: &impl ZabbixApiClient,
+        session: &str,
+        hostnames: &[String],
+    ) -> OperationResult<HashMap<String, String>> {
+        let unknown_hostnames: Vec<String> = {
+            let host_ids = self.lock_host_ids();
+            hostnames.iter()
+                .filter(|hostname| !host_ids.contains_key(*hostname))
+                .cloned()
+                .collect()
+        };
+
+        if !unknown_hostnames.is_empty() {
+            let found = find_zabbix_host_ids(zabbix_client, session, &unknown_hostnames)?;
+
+            let mut host_ids = self.lock_host_ids();
+
+            for hostname in unknown_hostnames {
+                let host_id = found.get(&hostname).cloned();
+                host_ids.insert(hostname, host_id);
+            }
+        }
+
+        let host_ids = self.lock_host_ids();
+
+        Ok(hostnames.iter()
+            .filter_map(|hostname| match host_ids.get(hostname) {
+                Some(Some(host_id)) => Some((hostname.to_string(), host_id.to_string())),
+                _ => None,
+            })
+            .collect())
+    }
+
+    fn lock_host_ids(&self) -> MutexGuard<'_, HashMap<String, Option<String>>> {
+        self.host_ids.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
 #[derive(Serialize)]
 struct HostFilter {
     pub host: Vec<String>,
 }
+
+#[cfg(test)]
+mod tests {
+    use reqwest::blocking::Client;
+    use zabbix_api::client::client::ZabbixApiClientImpl;
+
+    use crate::zabbix::host::HostIdCache;
+
+    #[test]
+    fn known_hosts_should_not_be_requested() {
+        // Nothing listens on this port, any request would fail
+        let zabbix_client = ZabbixApiClientImpl::new(Client::new(), "http://127.0.0.1:9/api_jsonrpc.php");
+
+        let host_cache = HostIdCache::default();
+        host_cache.insert("srv1", "10001");
+        host_cache.insert("srv2", "10002");
+
+        let host_ids = host_cache
+            .find_host_ids(&zabbix_client, "session", &["srv2".to_string(), "srv1".to_string()])
+            .unwrap();
+
+        assert_eq!(host_ids.len(), 2);
+        assert_eq!(host_ids["srv1"], "10001");
+        assert_eq!(host_cache.find_host_id(&zabbix_client, "session", "srv2").unwrap(), Some("10002".to_string()));
+    }
+}
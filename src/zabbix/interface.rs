@@ -0,0 +1,31 @@
+use serde_derive::Deserialize;
+use zabbix_api::client::client::ZabbixApiClient;
+
+use crate::types::OperationResult;
+use crate::zabbix::api::call_api_method;
+
+/// Zabbix API: https://www.zabbix.com/documentation/6.0/en/manual/api/reference/hostinterface/object
+#[derive(Deserialize, Clone, Debug)]
+pub struct ZabbixHostInterface {
+    #[serde(rename = "interfaceid")]
+    pub interface_id: String,
+
+    /// 1 - agent, 2 - SNMP, 3 - IPMI, 4 - JMX
+    pub r#type: String,
+
+    /// 1 - default interface of its type
+    pub main: String,
+}
+
+pub fn find_host_interfaces(
+    zabbix_client: &impl ZabbixApiClient,
+    session: &str,
+    host_id: &str,
+) -> OperationResult<Vec<ZabbixHostInterface>> {
+    let params = serde_json::json!({
+        "output": ["interfaceid", "type", "main"],
+        "hostids": [host_id],
+    });
+
+    call_api_method(zabbix_client, session, "hostinterface.get", &params)
+}
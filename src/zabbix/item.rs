@@ -0,0 +1,83 @@
+use serde_derive::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use zabbix_api::client::client::ZabbixApiClient;
+use zabbix_api::host::model::ZabbixHostTag;
+
+use crate::types::{EmptyResult, OperationResult};
+use crate::zabbix::api::{call_api_method, create_objects, update_object, TAG_OPERATOR_EQUALS};
+
+/// Zabbix API: https://www.zabbix.com/documentation/6.0/en/manual/api/reference/item/create
+#[derive(Serialize, Clone, Debug)]
+pub struct CreateItemRequest {
+    pub name: String,
+    pub key_: String,
+    #[serde(rename = "hostid")]
+    pub host_id: String,
+    pub r#type: u8,
+    pub value_type: u8,
+    #[serde(rename = "interfaceid")]
+    pub interface_id: String,
+    pub tags: Vec<ZabbixHostTag>,
+    pub delay: String,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct ZabbixItem {
+    #[serde(rename = "itemid")]
+    pub item_id: String,
+    pub key_: String,
+    #[serde(flatten)]
+    pub fields: Map<String, Value>,
+}
+
+/// Find items on the host by keys with a single request
+pub fn find_items(
+    zabbix_client: &impl ZabbixApiClient,
+    session: &str,
+    host_id: &str,
+    keys: &[String],
+) -> OperationResult<Vec<ZabbixItem>> {
+    let params = serde_json::json!({
+        "output": "extend",
+        "hostids": [host_id],
+        "filter": { "key_": keys },
+        "selectTags": "extend",
+    });
+
+    call_api_method(zabbix_client, session, "item.get", &params)
+}
+
+/// Find items on the host which have `tag` with exact `value`
+pub fn find_items_by_tag(
+    zabbix_client: &impl ZabbixApiClient,
+    session: &str,
+    host_id: &str,
+    tag: &str,
+    value: &str,
+) -> OperationResult<Vec<ZabbixItem>> {
+    let params = serde_json::json!({
+        "output": "extend",
+        "hostids": [host_id],
+        "tags": [{ "tag": tag, "value": value, "operator": TAG_OPERATOR_EQUALS }],
+    });
+
+    call_api_method(zabbix_client, session, "item.get", &params)
+}
+
+/// Create items with a single request, returns ids of created items
+pub fn create_items(
+    zabbix_client: &impl ZabbixApiClient,
+    session: &str,
+    requests: &[CreateItemRequest],
+) -> OperationResult<Vec<String>> {
+    create_objects(zabbix_client, session, "item.create", "itemids", requests)
+}
+
+pub fn update_item(
+    zabbix_client: &impl ZabbixApiClient,
+    session: &str,
+    item_id: &str,
+    fields: &Map<String, Value>,
+) -> EmptyResult {
+    update_object(zabbix_client, session, "item.update", "itemid", item_id, fields)
+}
@@ -1 +1,9 @@
-pub mod host;
\ No newline at end of file
+pub mod api;
+pub mod auth;
+pub mod diff;
+pub mod host;
+pub mod interface;
+pub mod item;
+pub mod rate;
+pub mod trigger;
+pub mod webscenario;
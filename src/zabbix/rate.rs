@@ -0,0 +1,54 @@
+use std::sync::Mutex;
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// Limits requests per second shared by all worker threads
+pub struct RateLimiter {
+    interval: Duration,
+    next_slot: Mutex<Instant>,
+}
+
+impl RateLimiter {
+    pub fn new(requests_per_second: u32) -> RateLimiter {
+        RateLimiter {
+            interval: Duration::from_secs(1) / requests_per_second.max(1),
+            next_slot: Mutex::new(Instant::now()),
+        }
+    }
+
+    /// Block current thread until request is allowed
+    pub fn acquire(&self) {
+        let now = Instant::now();
+
+        let slot = {
+            let mut next_slot = self.next_slot.lock().unwrap_or_else(|e| e.into_inner());
+            let slot = (*next_slot).max(now);
+            *next_slot = slot + self.interval;
+            slot
+        };
+
+        if slot > now {
+            thread::sleep(slot - now);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, Instant};
+
+    use crate::zabbix::rate::RateLimiter;
+
+    #[test]
+    fn requests_should_be_spread_by_interval() {
+        let rate_limiter = RateLimiter::new(100);
+
+        let started = Instant::now();
+
+        for _ in 0..5 {
+            rate_limiter.acquire();
+        }
+
+        assert!(started.elapsed() >= Duration::from_millis(40));
+    }
+}
@@ -0,0 +1,105 @@
+use serde_derive::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use zabbix_api::client::client::ZabbixApiClient;
+use zabbix_api::host::model::ZabbixHostTag;
+
+use crate::types::{EmptyResult, OperationResult, OptionalResult};
+use crate::zabbix::api::{call_api_method, create_objects, update_object, TAG_OPERATOR_EQUALS};
+
+/// Zabbix API: https://www.zabbix.com/documentation/6.0/en/manual/api/reference/trigger/create
+#[derive(Serialize, Clone, Debug)]
+pub struct CreateTriggerRequest {
+    pub description: String,
+    pub expression: String,
+    pub priority: u8,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub recovery_mode: Option<u8>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub recovery_expression: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub event_name: Option<String>,
+    pub dependencies: Vec<TriggerDependency>,
+    pub tags: Vec<ZabbixHostTag>,
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Clone, Debug)]
+pub struct TriggerDependency {
+    #[serde(rename = "triggerid")]
+    pub trigger_id: String,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct ZabbixTrigger {
+    #[serde(rename = "triggerid")]
+    pub trigger_id: String,
+    pub description: String,
+    #[serde(flatten)]
+    pub fields: Map<String, Value>,
+}
+
+pub fn find_trigger(
+    zabbix_client: &impl ZabbixApiClient,
+    session: &str,
+    host_id: &str,
+    description: &str,
+) -> OptionalResult<ZabbixTrigger> {
+    let triggers = find_triggers(zabbix_client, session, host_id, &[description.to_string()])?;
+
+    Ok(triggers.into_iter().next())
+}
+
+/// Find triggers on the host by descriptions with a single request
+pub fn find_triggers(
+    zabbix_client: &impl ZabbixApiClient,
+    session: &str,
+    host_id: &str,
+    descriptions: &[String],
+) -> OperationResult<Vec<ZabbixTrigger>> {
+    let params = serde_json::json!({
+        "output": "extend",
+        "hostids": [host_id],
+        "filter": { "description": descriptions },
+        "expandExpression": true,
+        "selectTags": "extend",
+        "selectDependencies": ["triggerid"],
+    });
+
+    call_api_method(zabbix_client, session, "trigger.get", &params)
+}
+
+/// Find triggers on the host which have `tag` with exact `value`
+pub fn find_triggers_by_tag(
+    zabbix_client: &impl ZabbixApiClient,
+    session: &str,
+    host_id: &str,
+    tag: &str,
+    value: &str,
+) -> OperationResult<Vec<ZabbixTrigger>> {
+    let params = serde_json::json!({
+        "output": "extend",
+        "hostids": [host_id],
+        "tags": [{ "tag": tag, "value": value, "operator": TAG_OPERATOR_EQUALS }],
+    });
+
+    call_api_method(zabbix_client, session, "trigger.get", &params)
+}
+
+/// Create triggers with a single request, returns ids of created triggers
+pub fn create_triggers(
+    zabbix_client: &impl ZabbixApiClient,
+    session: &str,
+    requests: &[CreateTriggerRequest],
+) -> OperationResult<Vec<String>> {
+    create_objects(zabbix_client, session, "trigger.create", "triggerids", requests)
+}
+
+pub fn update_trigger(
+    zabbix_client: &impl ZabbixApiClient,
+    session: &str,
+    trigger_id: &str,
+    fields: &Map<String, Value>,
+) -> EmptyResult {
+    update_object(zabbix_client, session, "trigger.update", "triggerid", trigger_id, fields)
+}
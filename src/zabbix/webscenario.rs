@@ -0,0 +1,188 @@
+use serde_derive::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use zabbix_api::client::client::ZabbixApiClient;
+use zabbix_api::host::model::ZabbixHostTag;
+
+use crate::config::ws::HttpField;
+use crate::types::{EmptyResult, OperationResult};
+use crate::zabbix::api::{
+    call_api_method, create_objects, update_object, TAG_OPERATOR_EQUALS, TAG_OPERATOR_EXISTS,
+};
+
+/// Zabbix API: https://www.zabbix.com/documentation/6.0/en/manual/api/reference/httptest/create
+#[derive(Serialize, Clone, Debug)]
+pub struct CreateWebScenarioRequest {
+    pub name: String,
+    #[serde(rename = "hostid")]
+    pub host_id: String,
+    pub delay: String,
+    pub retries: u8,
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub agent: String,
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub http_proxy: String,
+    pub headers: Vec<HttpField>,
+    pub variables: Vec<HttpField>,
+    pub steps: Vec<WebScenarioStep>,
+    pub tags: Vec<ZabbixHostTag>,
+}
+
+/// Zabbix API: https://www.zabbix.com/documentation/6.0/en/manual/api/reference/httptest/object#scenario-step
+#[derive(Serialize, Clone, Debug)]
+pub struct WebScenarioStep {
+    pub name: String,
+    pub no: u32,
+    pub url: String,
+    pub timeout: String,
+    pub status_codes: String,
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub required: String,
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub posts: String,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub variables: Vec<HttpField>,
+    pub follow_redirects: u8,
+    pub retrieve_mode: u8,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct ZabbixWebScenario {
+    #[serde(rename = "httptestid")]
+    pub httptest_id: String,
+    pub name: String,
+    #[serde(rename = "hostid")]
+    pub host_id: String,
+    #[serde(default)]
+    pub steps: Vec<ZabbixWebScenarioStep>,
+    #[serde(default)]
+    pub tags: Vec<ZabbixHostTag>,
+    #[serde(default, skip_serializing)]
+    pub hosts: Vec<ZabbixWebScenarioHost>,
+    #[serde(flatten)]
+    pub fields: Map<String, Value>,
+}
+
+impl ZabbixWebScenario {
+    pub fn get_url(&self) -> Option<&str> {
+        self.steps
+            .iter()
+            .find(|step| step.no == "1")
+            .or(self.steps.first())
+            .map(|step| step.url.as_str())
+    }
+
+    pub fn get_tag_value(&self, tag: &str) -> Option<&str> {
+        self.tags
+            .iter()
+            .find(|t| t.tag == tag)
+            .map(|t| t.value.as_str())
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct ZabbixWebScenarioStep {
+    pub no: String,
+    pub url: String,
+    #[serde(flatten)]
+    pub fields: Map<String, Value>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct ZabbixWebScenarioHost {
+    pub host: String,
+}
+
+/// Find web scenarios on the host by names with a single request
+pub fn find_web_scenarios(
+    zabbix_client: &impl ZabbixApiClient,
+    session: &str,
+    host_id: &str,
+    names: &[String],
+) -> OperationResult<Vec<ZabbixWebScenario>> {
+    let params = serde_json::json!({
+        "output": "extend",
+        "hostids": [host_id],
+        "filter": { "name": names },
+        "selectSteps": "extend",
+        "selectTags": "extend",
+    });
+
+    call_api_method(zabbix_client, session, "httptest.get", &params)
+}
+
+/// Find web scenarios on all hosts which have `tag` with any value
+pub fn find_web_scenarios_by_tag(
+    zabbix_client: &impl ZabbixApiClient,
+    session: &str,
+    tag: &str,
+) -> OperationResult<Vec<ZabbixWebScenario>> {
+    let params = serde_json::json!({
+        "output": "extend",
+        "tags": [{ "tag": tag, "operator": TAG_OPERATOR_EXISTS }],
+        "selectSteps": "extend",
+        "selectTags": "extend",
+        "selectHosts": ["host"],
+    });
+
+    call_api_method(zabbix_client, session, "httptest.get", &params)
+}
+
+/// Find web scenarios on all hosts which names start with `prefix`
+pub fn find_web_scenarios_by_name_prefix(
+    zabbix_client: &impl ZabbixApiClient,
+    session: &str,
+    prefix: &str,
+) -> OperationResult<Vec<ZabbixWebScenario>> {
+    let params = serde_json::json!({
+        "output": "extend",
+        "search": { "name": prefix },
+        "startSearch": true,
+        "selectSteps": "extend",
+        "selectTags": "extend",
+        "selectHosts": ["host"],
+    });
+
+    call_api_method(zabbix_client, session, "httptest.get", &params)
+}
+
+/// Find web scenarios on the host which have `tag` with any of `values`.
+/// Conditions of the same tag are combined with OR
+pub fn find_web_scenarios_by_tag_values(
+    zabbix_client: &impl ZabbixApiClient,
+    session: &str,
+    host_id: &str,
+    tag: &str,
+    values: &[String],
+) -> OperationResult<Vec<ZabbixWebScenario>> {
+    let tags: Vec<Value> = values.iter()
+        .map(|value| serde_json::json!({ "tag": tag, "value": value, "operator": TAG_OPERATOR_EQUALS }))
+        .collect();
+
+    let params = serde_json::json!({
+        "output": "extend",
+        "hostids": [host_id],
+        "tags": tags,
+        "selectSteps": "extend",
+        "selectTags": "extend",
+    });
+
+    call_api_method(zabbix_client, session, "httptest.get", &params)
+}
+
+/// Create web scenarios with a single request, returns ids of created web scenarios
+pub fn create_web_scenarios(
+    zabbix_client: &impl ZabbixApiClient,
+    session: &str,
+    requests: &[CreateWebScenarioRequest],
+) -> OperationResult<Vec<String>> {
+    create_objects(zabbix_client, session, "httptest.create", "httptestids", requests)
+}
+
+pub fn update_web_scenario(
+    zabbix_client: &impl ZabbixApiClient,
+    session: &str,
+    httptest_id: &str,
+    fields: &Map<String, Value>,
+) -> EmptyResult {
+    update_object(zabbix_client, session, "httptest.update", "httptestid", httptest_id, fields)
+}